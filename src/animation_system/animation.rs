@@ -1,9 +1,8 @@
-use crate::asset_system::AssetManager;
 use crate::utils::Direction;
 
 use ggez::{
     timer,
-    graphics::{self, DrawParam, Image},
+    graphics::{self, Color, DrawParam, Image},
     mint::Point2,
     Context, GameResult,
 };
@@ -21,8 +20,8 @@ impl Animation {
         &mut self,
         ctx: &mut Context,
         direction: Direction,
-        asset_manager: &AssetManager,
         position: Point2<f32>,
+        color: Color,
     ) -> GameResult {
         let duration = timer::time_since_start(ctx);
         let current_time = duration.as_millis();
@@ -39,10 +38,11 @@ impl Animation {
 
         graphics::draw(
             ctx,
-            &asset_manager.monster_assets.chicken_assets.walking_sprites[self.current_sprite],
+            &self.sprites[self.current_sprite],
             DrawParam::default()
                         .scale(scale)
-                        .dest(position),
+                        .dest(position)
+                        .color(color),
         )?;
 
         Ok(())
@@ -1,16 +1,14 @@
-use crate::asset_system::monster_assets::MonsterAssets;
+use crate::{
+    asset_system::{MonsterAssets, TowerAssets},
+    game_components::{monsters::MonsterRegistry, towers::TowerRegistry},
+};
 
-use ggez::{audio, graphics, Context};
-
-pub struct TowerAssets {
-    pub tower_sprite: graphics::Image,
-    pub tower_ninja_sprite: graphics::Image,
-    pub tower_attack_sound: audio::Source,
-    pub ninja_tower_strong_attack_sound: audio::Source,
-}
+use ggez::{audio, graphics, Context, GameResult};
 
 pub struct ItemAssets {
     pub gold_sprite: graphics::Image,
+    pub heal_sprite: graphics::Image,
+    pub damage_buff_sprite: graphics::Image,
     pub gold_sound: audio::Source,
 }
 
@@ -18,54 +16,37 @@ pub struct BaseAssets {
     pub base_sprite: graphics::Image,
 }
 
-pub struct BuilderUIAssets {
-    pub tower_sprite: graphics::Image,
-    pub tower_selected_sprite: graphics::Image,
-    pub ninja_tower_sprite: graphics::Image,
-    pub ninja_tower_selected_sprite: graphics::Image,
-}
-
 pub struct AssetManager {
     pub tower_assets: TowerAssets,
     pub monster_assets: MonsterAssets,
     pub item_assets: ItemAssets,
     pub base_assets: BaseAssets,
-    pub builder_ui_assets: BuilderUIAssets,
 }
 
 impl AssetManager {
-    pub fn new(ctx: &mut Context) -> AssetManager {
-        let tower_assets = TowerAssets {
-            tower_sprite: graphics::Image::new(ctx, "/tower2.png").unwrap(),
-            tower_ninja_sprite: graphics::Image::new(ctx, "/tower_ninja.png").unwrap(),
-            tower_attack_sound: audio::Source::new(ctx, "/tower_attack_pop.ogg").unwrap(),
-            ninja_tower_strong_attack_sound: audio::Source::new(ctx, "/tower_attack_pop.ogg")
-                .unwrap(),
-        };
-
+    /// Loads the assets of the game and of every monster and tower type in
+    /// the registries.
+    pub fn new(
+        ctx: &mut Context,
+        monster_registry: &MonsterRegistry,
+        tower_registry: &TowerRegistry,
+    ) -> GameResult<AssetManager> {
         let item_assets = ItemAssets {
-            gold_sprite: graphics::Image::new(ctx, "/gold_pile.png").unwrap(),
-            gold_sound: audio::Source::new(ctx, "/gold.ogg").unwrap(),
+            gold_sprite: graphics::Image::new(ctx, "/gold_pile.png")?,
+            heal_sprite: graphics::Image::new(ctx, "/heal_pickup.png")?,
+            damage_buff_sprite: graphics::Image::new(ctx, "/damage_buff_pickup.png")?,
+            gold_sound: audio::Source::new(ctx, "/gold.ogg")?,
         };
 
         let base_assets = BaseAssets {
-            base_sprite: graphics::Image::new(ctx, "/base.png").unwrap(),
-        };
-
-        let builder_ui_assets = BuilderUIAssets {
-            tower_sprite: graphics::Image::new(ctx, "/ui/tower.png").unwrap(),
-            tower_selected_sprite: graphics::Image::new(ctx, "/ui/tower_selected.png").unwrap(),
-            ninja_tower_sprite: graphics::Image::new(ctx, "/ui/ninja_tower.png").unwrap(),
-            ninja_tower_selected_sprite: graphics::Image::new(ctx, "/ui/ninja_tower_selected.png")
-                .unwrap(),
+            base_sprite: graphics::Image::new(ctx, "/base.png")?,
         };
 
-        AssetManager {
-            tower_assets,
-            monster_assets: MonsterAssets::new(ctx),
+        Ok(AssetManager {
+            tower_assets: TowerAssets::new(ctx, tower_registry)?,
+            monster_assets: MonsterAssets::new(ctx, monster_registry)?,
             item_assets,
             base_assets,
-            builder_ui_assets,
-        }
+        })
     }
 }
@@ -1,11 +1,12 @@
 pub mod asset_manager;
 pub mod monster_assets;
+pub mod tower_assets;
 
 // Re-export assets.
 pub use self::asset_manager::AssetManager;
+#[allow(unused_imports)]
 pub use self::asset_manager::BaseAssets;
-pub use self::asset_manager::BuilderUIAssets;
+#[allow(unused_imports)]
 pub use self::asset_manager::ItemAssets;
-pub use self::asset_manager::TowerAssets;
-pub use self::monster_assets::ChickenAssets;
 pub use self::monster_assets::MonsterAssets;
+pub use self::tower_assets::TowerAssets;
@@ -1,31 +1,50 @@
-use ggez::{audio, graphics, Context};
+use crate::game_components::monsters::MonsterRegistry;
 
-pub struct ChickenAssets {
+use ggez::{audio, graphics, Context, GameResult};
+
+use std::collections::HashMap;
+
+/// Sprites and sounds of a single monster type.
+pub struct MonsterAssetSet {
     pub walking_sprites: Vec<graphics::Image>,
+    pub hurt_sound: audio::Source,
 }
 
+/// Assets of every monster type in the registry, by monster name.
 pub struct MonsterAssets {
-    pub chicken_assets: ChickenAssets,
-    pub cool_chicken_sprite: graphics::Image,
-    pub monster_hurt_sound: audio::Source,
+    asset_sets: HashMap<String, MonsterAssetSet>,
 }
 
 impl MonsterAssets {
-    pub fn new(ctx: &mut Context) -> MonsterAssets {
-        let mut walking_sprites = Vec::new();
-        walking_sprites
-            .push(graphics::Image::new(ctx, "/monsters/chicken/chicken_run1.png").unwrap());
-        walking_sprites
-            .push(graphics::Image::new(ctx, "/monsters/chicken/chicken_run2.png").unwrap());
-
-        MonsterAssets {
-            chicken_assets: ChickenAssets { walking_sprites },
-            cool_chicken_sprite: graphics::Image::new(
-                ctx,
-                "/monsters/cool_chicken/cool_chicken.png",
-            )
-            .unwrap(),
-            monster_hurt_sound: audio::Source::new(ctx, "/monsters/chicken_hurt.ogg").unwrap(),
+    /// Loads the assets of every monster type in the registry. Fails if any
+    /// of the sprites or sounds they refer to can't be loaded.
+    pub fn new(ctx: &mut Context, monster_registry: &MonsterRegistry) -> GameResult<MonsterAssets> {
+        let mut asset_sets = HashMap::new();
+
+        for definition in monster_registry.definitions() {
+            let walking_sprites = definition
+                .walking_sprites
+                .iter()
+                .map(|path| graphics::Image::new(ctx, path))
+                .collect::<GameResult<_>>()?;
+
+            asset_sets.insert(
+                definition.name.clone(),
+                MonsterAssetSet {
+                    walking_sprites,
+                    hurt_sound: audio::Source::new(ctx, &definition.hurt_sound)?,
+                },
+            );
         }
+
+        Ok(MonsterAssets { asset_sets })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MonsterAssetSet> {
+        self.asset_sets.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut MonsterAssetSet> {
+        self.asset_sets.get_mut(name)
     }
 }
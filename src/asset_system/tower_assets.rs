@@ -0,0 +1,47 @@
+use crate::game_components::towers::TowerRegistry;
+
+use ggez::{audio, graphics, Context, GameResult};
+
+use std::collections::HashMap;
+
+/// Sprites of every tower type in the registry (including the sprites its
+/// upgrades switch to and its build bar icons) by path, and the assets shared
+/// by all towers.
+pub struct TowerAssets {
+    sprites: HashMap<String, graphics::Image>,
+    pub cannonball_sprite: graphics::Image,
+    pub shuriken_sprite: graphics::Image,
+    pub tower_attack_sound: audio::Source,
+    pub strike_sound: audio::Source,
+}
+
+impl TowerAssets {
+    /// Loads the assets of every tower type in the registry. Fails if any of
+    /// the sprites or sounds can't be loaded.
+    pub fn new(ctx: &mut Context, tower_registry: &TowerRegistry) -> GameResult<TowerAssets> {
+        let mut sprites = HashMap::new();
+
+        for definition in tower_registry.definitions() {
+            let paths = definition
+                .sprites()
+                .chain([&definition.icon, &definition.icon_selected]);
+            for path in paths {
+                if !sprites.contains_key(path) {
+                    sprites.insert(path.clone(), graphics::Image::new(ctx, path)?);
+                }
+            }
+        }
+
+        Ok(TowerAssets {
+            sprites,
+            cannonball_sprite: graphics::Image::new(ctx, "/cannonball.png")?,
+            shuriken_sprite: graphics::Image::new(ctx, "/shuriken.png")?,
+            tower_attack_sound: audio::Source::new(ctx, "/tower_attack_pop.ogg")?,
+            strike_sound: audio::Source::new(ctx, "/tower_attack_pop.ogg")?,
+        })
+    }
+
+    pub fn get_sprite(&self, path: &str) -> Option<&graphics::Image> {
+        self.sprites.get(path)
+    }
+}
@@ -6,14 +6,26 @@ use ggez::{
     Context, GameResult,
 };
 
-pub const BASE_SIZE: f32 = 60.0;
 pub const BASE_PADDING: f32 = 5.0;
+/// Number of blocks the base covers in each direction.
+pub const BASE_BLOCKS: i32 = 2;
 
 pub struct Base {
     pub position: [f32; 2],
 }
 
 impl Base {
+    /// All blocks covered by a base with its top left corner at `position`.
+    pub fn blocks_covered_at(position: [i32; 2]) -> Vec<[i32; 2]> {
+        let mut blocks = Vec::new();
+        for dy in 0..BASE_BLOCKS {
+            for dx in 0..BASE_BLOCKS {
+                blocks.push([position[0] + dx, position[1] + dy]);
+            }
+        }
+        blocks
+    }
+
     pub fn draw(&mut self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult {
         let location = Point2 {
             x: self.position[0] * BLOCK_SIZE + BASE_PADDING,
@@ -28,11 +40,4 @@ impl Base {
 
         Ok(())
     }
-
-    pub fn is_position_in_base(&self, position: [f32; 2]) -> bool {
-        position[0] >= self.position[0] * BLOCK_SIZE - BASE_PADDING
-            && position[0] <= self.position[0] * BLOCK_SIZE + BASE_SIZE + 2.0 * BASE_PADDING
-            && position[1] >= self.position[1] * BLOCK_SIZE
-            && position[1] <= self.position[1] * BLOCK_SIZE + BASE_SIZE + 2.0 * BASE_PADDING
-    }
 }
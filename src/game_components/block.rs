@@ -1,3 +1,5 @@
+use crate::game_components::Terrain;
+
 use ggez::mint::Point2;
 use ggez::{graphics, Context, GameResult};
 
@@ -5,15 +7,25 @@ pub const BLOCK_SIZE: f32 = 35.0;
 
 pub struct Block {
     pub position: [f32; 2],
+    pub terrain: Terrain,
 }
 
 impl Block {
     pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let color = match self.terrain {
+            Terrain::Grass => graphics::Color::new(0.2, 0.5, 0.1, 1.0),
+            Terrain::Rock => graphics::Color::new(0.45, 0.45, 0.45, 1.0),
+            Terrain::Water => graphics::Color::new(0.15, 0.45, 0.8, 1.0),
+            Terrain::HighGround => graphics::Color::new(0.55, 0.5, 0.3, 1.0),
+            Terrain::Path => graphics::Color::new(0.1, 0.4, 0.0, 1.0),
+            Terrain::MudPath => graphics::Color::new(0.35, 0.25, 0.1, 1.0),
+        };
+
         let rectangle = graphics::Mesh::new_rectangle(
             ctx,
             graphics::DrawMode::fill(),
             [0.0, 0.0, BLOCK_SIZE, BLOCK_SIZE].into(),
-            ggez::graphics::Color::new(0.1, 0.4, 0.0, 1.0),
+            color,
         )?;
 
         let location = (Point2 {
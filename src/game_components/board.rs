@@ -1,242 +1,902 @@
-use crate::game_components::{towers::Tower, Base, Block, GoldPile, BLOCK_SIZE};
+use crate::asset_system::AssetManager;
+use crate::game_components::{
+    base::BASE_BLOCKS,
+    grid::{adjacent_blocks, Grid, Occupant},
+    monsters::{Aura, AuraEffects, Monster, MonsterId, MonsterState, PathFollower},
+    obstacle::Obstacle,
+    pathfinding::find_path,
+    towers::{Tower, TowerDefinition, TowerId},
+    Base, Block, Pickup, PickupKind, Projectile, Terrain, TerrainArea, BLOCK_SIZE,
+};
 use crate::game_views::monsters::MonsterView;
+use crate::level_system::{LevelError, MapDefinition};
+
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+
+use serde::Deserialize;
+
+/// Number of blocks that fit horizontally inside the game area.
+pub const BOARD_WIDTH: i32 = 22;
+/// Number of blocks that fit vertically inside the game area (above the UI).
+pub const BOARD_HEIGHT: i32 = 12;
+
+/// Share of the invested gold refunded for a sold tower, unless the map
+/// says otherwise.
+pub const DEFAULT_SELL_REFUND: f32 = 0.7;
+
+/// Number of fresh starting positions `Board::generate` tries, and how many
+/// search steps each attempt may take, before settling for the longest path
+/// it has found so far.
+const GENERATION_ATTEMPTS: u32 = 20;
+const GENERATION_STEPS_PER_ATTEMPT: u32 = 5_000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Deserialize)]
+pub enum BoardMode {
+    /// Monsters walk along the fixed routes of the board.
+    #[default]
+    Path,
+    /// Open field without a fixed path, monsters find their own way to the
+    /// base and towers act as walls.
+    Maze,
+}
 
 pub struct Board {
+    pub mode: BoardMode,
+    /// What occupies each block of the board.
+    pub grid: Grid,
+    /// Every block that is part of at least one route, each block only once.
+    /// In maze mode only the entrances.
     pub path_blocks: Vec<Block>,
+    /// Blocks of any other terrain than grass that aren't part of a route.
+    pub terrain_blocks: Vec<Block>,
+    /// Block positions monsters walk along, from an entrance to the base.
+    /// Routes may share blocks, i.e. fork and merge. In maze mode there is
+    /// one route per entrance, kept up to date as towers are placed.
+    pub routes: Vec<Vec<[f32; 2]>>,
+    pub entrances: Vec<[f32; 2]>,
+    pub obstacles: Vec<Obstacle>,
     pub towers: Vec<Box<dyn Tower>>,
+    next_tower_id: TowerId,
+    /// Whether the board is between waves, as signalled by the monster
+    /// spawner with `start_wave` and `start_build_phase`.
+    build_phase: bool,
+    /// Towers placed during the current build phase, which are sold for a
+    /// full refund.
+    refundable: Vec<TowerId>,
+    /// Share of the invested gold refunded for a sold tower placed before
+    /// the current build phase.
+    pub sell_refund: f32,
     pub monster_views: Vec<Box<dyn MonsterView>>,
-    pub gold_piles: Vec<GoldPile>,
+    next_monster_id: MonsterId,
+    pub pickups: Vec<Pickup>,
+    /// Projectiles fired by towers, still flying.
+    pub projectiles: Vec<Projectile>,
     pub base: Base,
 }
 
 impl Board {
-    pub fn generate(_seed: u64, _length: u32) -> Board {
+    /// Generates a board with a random path of (up to) `length` blocks
+    /// starting at the left edge of the board, with the base placed at the
+    /// end of the path. The same seed always generates the same board.
+    ///
+    /// The path is contiguous and never touches itself, so every path block
+    /// only has its predecessor and successor as neighbors. If no such path
+    /// of the requested length fits on the board the longest one found is
+    /// used instead.
+    pub fn generate(seed: u64, length: u32) -> Board {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let path = Board::generate_path(&mut rng, length as usize);
+
+        let base_position = if path.is_empty() {
+            [0, rng.gen_range(0..BOARD_HEIGHT - 1)]
+        } else {
+            *Board::base_candidates(&path)
+                .choose(&mut rng)
+                .expect("generated path should always leave room for the base")
+        };
+
+        debug!(
+            "generate: seed ({}), length ({}), generated path length ({}), base ({:?}).",
+            seed,
+            length,
+            path.len(),
+            base_position
+        );
+
+        let entrances = path.first().copied().into_iter().collect();
+
+        Board::new(
+            BoardMode::Path,
+            [BOARD_WIDTH, BOARD_HEIGHT],
+            vec![path],
+            entrances,
+            &[],
+            &[],
+            base_position,
+        )
+    }
+
+    /// Builds a board from a map definition, validating it first.
+    pub fn from_map(map: &MapDefinition) -> Result<Board, LevelError> {
+        map.validate()?;
+
+        let entrances = match map.mode {
+            BoardMode::Path => {
+                let mut entrances = Vec::new();
+                for &start in map.routes.iter().filter_map(|route| route.first()) {
+                    if !entrances.contains(&start) {
+                        entrances.push(start);
+                    }
+                }
+                entrances
+            }
+            BoardMode::Maze => map.entrances.clone(),
+        };
+
+        let mut board = Board::new(
+            map.mode,
+            [map.width, map.height],
+            map.routes.clone(),
+            entrances,
+            &map.unbuildable,
+            &map.terrain,
+            map.base,
+        );
+
+        for definition in map.obstacles.iter() {
+            board.add_obstacle(Obstacle::new(definition));
+        }
+        // Maze routes go around the obstacles.
+        board.recompute_routes();
+        board.sell_refund = map.sell_refund;
+
+        Ok(board)
+    }
+
+    /// Sets up the board and its occupancy grid, all positions in blocks.
+    /// Route blocks get path terrain, unless a terrain area marks them as
+    /// mud. In maze mode the caller computes the routes with
+    /// `recompute_routes`.
+    fn new(
+        mode: BoardMode,
+        size: [i32; 2],
+        routes: Vec<Vec<[i32; 2]>>,
+        entrances: Vec<[i32; 2]>,
+        unbuildable: &[[i32; 2]],
+        terrain: &[TerrainArea],
+        base: [i32; 2],
+    ) -> Board {
+        let mut grid = Grid::new(size[0], size[1]);
+
+        for area in terrain.iter() {
+            for block in area.blocks() {
+                grid.set_terrain(block, area.terrain);
+            }
+        }
+
         let mut path_blocks = Vec::new();
-        path_blocks.push(Block {
-            position: [0.0, 0.0],
-        });
-        path_blocks.push(Block {
-            position: [0.0, 1.0],
-        });
-        path_blocks.push(Block {
-            position: [0.0, 2.0],
-        });
-        path_blocks.push(Block {
-            position: [1.0, 2.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 2.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 3.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 3.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 4.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [3.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [4.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [5.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [6.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [7.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [8.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [9.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [10.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [11.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [12.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [13.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [14.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [15.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [16.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [17.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [18.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [19.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [20.0, 5.0],
-        });
-        path_blocks.push(Block {
-            position: [20.0, 6.0],
-        });
-        path_blocks.push(Block {
-            position: [20.0, 7.0],
-        });
-        path_blocks.push(Block {
-            position: [20.0, 8.0],
-        });
-        path_blocks.push(Block {
-            position: [20.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [19.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [18.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [17.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [16.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [15.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [14.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [13.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [12.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [11.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [10.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [9.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [8.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [7.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [6.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [5.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [4.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [3.0, 9.0],
-        });
-        path_blocks.push(Block {
-            position: [2.0, 9.0],
-        });
+        for &block in routes.iter().flatten().chain(entrances.iter()) {
+            if grid.get(block) != Some(Occupant::Path) {
+                grid.set(block, Occupant::Path);
+                if grid.terrain(block) != Terrain::MudPath {
+                    grid.set_terrain(block, Terrain::Path);
+                }
+                path_blocks.push(Block {
+                    position: to_position(block),
+                    terrain: grid.terrain(block),
+                });
+            }
+        }
+
+        let mut terrain_blocks = Vec::new();
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let terrain = grid.terrain([x, y]);
+                if terrain != Terrain::Grass && grid.get([x, y]) != Some(Occupant::Path) {
+                    terrain_blocks.push(Block {
+                        position: to_position([x, y]),
+                        terrain,
+                    });
+                }
+            }
+        }
+
+        for &block in unbuildable.iter() {
+            grid.set(block, Occupant::Unbuildable);
+        }
+
+        for block in Base::blocks_covered_at(base) {
+            grid.set(block, Occupant::Base);
+        }
 
         Board {
+            mode,
+            grid,
             path_blocks,
+            terrain_blocks,
+            routes: routes
+                .iter()
+                .map(|route| route.iter().map(|&block| to_position(block)).collect())
+                .collect(),
+            entrances: entrances.iter().map(|&block| to_position(block)).collect(),
+            obstacles: Vec::new(),
             towers: Vec::new(),
+            next_tower_id: 0,
+            build_phase: true,
+            refundable: Vec::new(),
+            sell_refund: DEFAULT_SELL_REFUND,
             monster_views: Vec::new(),
-            gold_piles: Vec::new(),
+            next_monster_id: 0,
+            pickups: Vec::new(),
+            projectiles: Vec::new(),
             base: Base {
-                position: [0.0, 8.0],
+                position: to_position(base),
             },
         }
     }
 
-    pub fn position_is_occupied(&self, click_position: [f32; 2]) -> bool {
-        let block_position = [
-            (click_position[0] / BLOCK_SIZE).floor(),
-            (click_position[1] / BLOCK_SIZE).floor(),
+    /// The entrances of the board, in the order they first appear among the
+    /// routes (or as listed by the map in maze mode).
+    pub fn spawn_points(&self) -> Vec<[f32; 2]> {
+        self.entrances.clone()
+    }
+
+    /// Picks one of the routes starting at the given entrance, choosing
+    /// randomly between lanes where the path forks.
+    pub fn pick_route(&self, entrance: usize) -> Option<Vec<[f32; 2]>> {
+        let spawn_point = *self.entrances.get(entrance)?;
+
+        let candidates: Vec<&Vec<[f32; 2]>> = self
+            .routes
+            .iter()
+            .filter(|route| route.first() == Some(&spawn_point))
+            .collect();
+
+        candidates
+            .choose(&mut thread_rng())
+            .map(|route| route.to_vec())
+    }
+
+    /// Route of flying monsters from the given entrance, straight to the
+    /// center of the base.
+    pub fn flight_route(&self, entrance: usize) -> Option<Vec<[f32; 2]>> {
+        let spawn_point = *self.entrances.get(entrance)?;
+        let base_center = [
+            self.base.position[0] + (BASE_BLOCKS - 1) as f32 / 2.0,
+            self.base.position[1] + (BASE_BLOCKS - 1) as f32 / 2.0,
         ];
+        Some(vec![spawn_point, base_center])
+    }
 
-        for tower in self.towers.iter() {
-            if tower.get_block_position() == block_position {
-                return true;
-            }
+    /// Absolute (pixel) position to spawn a monster at, the center of the
+    /// first block of its route moved `lateral_offset` pixels sideways,
+    /// across the direction the route leaves the block in.
+    pub fn spawn_position(route: &[[f32; 2]], lateral_offset: f32) -> Option<[f32; 2]> {
+        if route.is_empty() {
+            return None;
         }
+        Some(PathFollower::waypoint(route, 0, lateral_offset))
+    }
 
-        for path_block in self.path_blocks.iter() {
-            if path_block.position == block_position {
-                return true;
+    /// Finds the shortest walkable route from `start` to the base, treating
+    /// `extra_wall` as if a tower was already placed there.
+    fn find_route(&self, start: [i32; 2], extra_wall: Option<[i32; 2]>) -> Option<Vec<[f32; 2]>> {
+        let base = to_block(self.base.position);
+        let base_blocks = Base::blocks_covered_at(base);
+
+        let goals: Vec<[i32; 2]> = base_blocks
+            .iter()
+            .flat_map(|&block| self.grid.neighbors(block))
+            .filter(|&block| Some(block) != extra_wall && self.is_walkable(block))
+            .collect();
+
+        let path = find_path(&self.grid, start, &goals, |block| {
+            Some(block) != extra_wall && self.is_walkable(block)
+        })?;
+
+        Some(path.iter().map(|&block| to_position(block)).collect())
+    }
+
+    /// Whether monsters can walk through the block in maze mode.
+    fn is_walkable(&self, block: [i32; 2]) -> bool {
+        self.grid.is_passable(block) && self.grid.terrain(block).is_walkable()
+    }
+
+    /// Recomputes the route of every entrance and every monster on the board
+    /// in maze mode, e.g. after a tower has been placed or removed. Monsters
+    /// continue from the block they are currently on.
+    pub fn recompute_routes(&mut self) {
+        if self.mode != BoardMode::Maze {
+            return;
+        }
+
+        self.routes = self
+            .entrances
+            .iter()
+            .map(|entrance| {
+                self.find_route(to_block(*entrance), None)
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        // Flying monsters don't care about towers in the way.
+        let monster_routes: Vec<Option<Vec<[f32; 2]>>> = self
+            .monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .map(|monster| {
+                if monster.is_flying() {
+                    None
+                } else {
+                    self.find_route(block_of(monster.get_center_pos_abs()), None)
+                }
+            })
+            .collect();
+
+        for (monster_view, route) in self.monster_views.iter_mut().zip(monster_routes) {
+            if let Some(route) = route {
+                monster_view.get_monster_mut().set_route(route);
             }
         }
+    }
+
+    /// Whether a tower of the given type may be placed at the clicked
+    /// position. Besides the position being free and the terrain buildable
+    /// for the tower, in maze mode the tower may not cut off any entrance or
+    /// monster from the base.
+    pub fn can_place_tower(&self, click_position: [f32; 2], tower: &TowerDefinition) -> bool {
+        if self.position_is_occupied(click_position) {
+            return false;
+        }
 
-        if self.base.is_position_in_base(click_position) {
+        let terrain = self.grid.terrain(block_of(click_position));
+        if !terrain.is_buildable(tower.can_build_on_water()) {
+            return false;
+        }
+
+        if self.mode != BoardMode::Maze {
             return true;
         }
 
-        return false;
+        let wall = block_of(click_position);
+        let mut starts: Vec<[i32; 2]> = self
+            .entrances
+            .iter()
+            .map(|entrance| to_block(*entrance))
+            .collect();
+        starts.extend(
+            self.monster_views
+                .iter()
+                .map(|monster_view| monster_view.get_monster())
+                .filter(|monster| !monster.is_flying())
+                .map(|monster| block_of(monster.get_center_pos_abs())),
+        );
+
+        starts
+            .iter()
+            .all(|&start| self.find_route(start, Some(wall)).is_some())
+    }
+
+    /// Depth first search with backtracking for a non-self-touching path
+    /// starting at the left edge of the board.
+    fn generate_path(rng: &mut StdRng, length: usize) -> Vec<[i32; 2]> {
+        let mut best = Vec::new();
+        if length == 0 {
+            return best;
+        }
+
+        for _ in 0..GENERATION_ATTEMPTS {
+            let mut path = vec![[0, rng.gen_range(0..BOARD_HEIGHT)]];
+            let mut candidates = vec![Board::path_candidates(rng, &path)];
+
+            let mut steps = 0;
+            while !path.is_empty() && steps < GENERATION_STEPS_PER_ATTEMPT {
+                steps += 1;
+
+                let has_base = !Board::base_candidates(&path).is_empty();
+                if has_base && path.len() > best.len() {
+                    best = path.clone();
+                }
+
+                if path.len() == length {
+                    if has_base {
+                        return path;
+                    }
+                    // No room for the base here, keep searching.
+                    path.pop();
+                    candidates.pop();
+                    continue;
+                }
+
+                match candidates.last_mut().and_then(|c| c.pop()) {
+                    Some(next) => {
+                        path.push(next);
+                        candidates.push(Board::path_candidates(rng, &path));
+                    }
+                    None => {
+                        // Dead end, backtrack.
+                        path.pop();
+                        candidates.pop();
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Shuffled list of blocks the path can continue to from its last block.
+    fn path_candidates(rng: &mut StdRng, path: &[[i32; 2]]) -> Vec<[i32; 2]> {
+        let last = path[path.len() - 1];
+
+        let mut candidates: Vec<[i32; 2]> = adjacent_blocks(last)
+            .iter()
+            .copied()
+            .filter(|&candidate| {
+                is_on_board(candidate)
+                    && !path.contains(&candidate)
+                    // Only allowed to touch the block we came from.
+                    && adjacent_blocks(candidate)
+                        .iter()
+                        .all(|n| *n == last || !path.contains(n))
+            })
+            .collect();
+
+        candidates.shuffle(rng);
+        candidates
+    }
+
+    /// All base positions (top left block) where the base fits next to the
+    /// last block of the path without covering any path block. The base
+    /// covers 2x2 blocks.
+    fn base_candidates(path: &[[i32; 2]]) -> Vec<[i32; 2]> {
+        let [x, y] = match path.last() {
+            Some(last) => *last,
+            None => return Vec::new(),
+        };
+
+        let candidates = [
+            [x + 1, y - 1],
+            [x + 1, y],
+            [x - 2, y - 1],
+            [x - 2, y],
+            [x - 1, y + 1],
+            [x, y + 1],
+            [x - 1, y - 2],
+            [x, y - 2],
+        ];
+
+        candidates
+            .iter()
+            .copied()
+            .filter(|&base| {
+                Base::blocks_covered_at(base)
+                    .iter()
+                    .all(|block| is_on_board(*block) && !path.contains(block))
+            })
+            .collect()
+    }
+
+    /// Whether anything (or the edge of the board) prevents building at the
+    /// clicked position.
+    pub fn position_is_occupied(&self, click_position: [f32; 2]) -> bool {
+        !self.grid.is_free(block_of(click_position))
+    }
+
+    /// Puts an obstacle on the board, blocking the block for towers and
+    /// monsters until it is cleared.
+    pub fn add_obstacle(&mut self, obstacle: Obstacle) {
+        self.grid
+            .set(to_block(obstacle.position), Occupant::Obstacle);
+        self.obstacles.push(obstacle);
+    }
+
+    /// The obstacle at the clicked position, if any.
+    pub fn obstacle_at(&self, click_position: [f32; 2]) -> Option<&Obstacle> {
+        let block = block_of(click_position);
+        self.obstacles
+            .iter()
+            .find(|obstacle| to_block(obstacle.position) == block)
+    }
+
+    /// Removes the obstacle at the clicked position, freeing the block. If
+    /// the obstacle drops gold a gold pickup is left behind, to be picked up
+    /// like the loot dropped by monsters. Paying for it is up to the caller.
+    pub fn clear_obstacle(&mut self, click_position: [f32; 2]) -> Option<Obstacle> {
+        let block = block_of(click_position);
+        let index = self
+            .obstacles
+            .iter()
+            .position(|obstacle| to_block(obstacle.position) == block)?;
+        let obstacle = self.obstacles.remove(index);
+
+        debug!(
+            "Clearing {:?} at position {:?}.",
+            obstacle.kind, obstacle.position
+        );
+        self.grid.set(block, Occupant::Empty);
+
+        if obstacle.gold_drop > 0 {
+            self.pickups.push(Pickup {
+                position: [
+                    obstacle.position[0] * BLOCK_SIZE,
+                    obstacle.position[1] * BLOCK_SIZE,
+                ],
+                kind: PickupKind::Gold(obstacle.gold_drop),
+            });
+        }
+
+        self.recompute_routes();
+        Some(obstacle)
     }
 
     /// Special function used to ensure Towers are sorted by y position, this
     /// is required since ggez does not have z-indexing.
-    pub fn add_tower(&mut self, tower: Box<dyn Tower>) {
+    pub fn add_tower(&mut self, mut tower: Box<dyn Tower>) {
         debug!(
             "Trying to place new tower at position {:?}.",
             tower.get_block_position()
         );
 
+        let id = self.next_tower_id;
+        self.next_tower_id += 1;
+        tower.set_id(id);
+
+        let position = tower.get_block_position();
+        self.grid.set(to_block(position), Occupant::Tower(id));
+        tower.set_terrain(self.grid.terrain(to_block(position)));
+
         // Find index where to insert new tower, based on sorted y position.
-        let mut index = 0;
-        for i in 0..self.towers.iter().len() {
-            if self.towers[i].get_block_position()[1] >= tower.get_block_position()[1] {
-                break;
-            }
-            index += 1;
+        let index = self
+            .towers
+            .partition_point(|t| t.get_block_position()[1] < position[1]);
+
+        if self.build_phase {
+            self.refundable.push(id);
         }
 
-        debug!("New tower put at list index {}.", index);
+        debug!("New tower {} put at list index {}.", tower.get_id(), index);
         self.towers.insert(index, tower);
+
+        self.recompute_routes();
+    }
+
+    /// Id of the tower at the clicked position, if any.
+    pub fn tower_at(&self, click_position: [f32; 2]) -> Option<TowerId> {
+        match self.grid.get(block_of(click_position)) {
+            Some(Occupant::Tower(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    pub fn get_tower(&self, id: TowerId) -> Option<&dyn Tower> {
+        self.towers
+            .iter()
+            .find(|tower| tower.get_id() == id)
+            .map(|tower| tower.as_ref())
+    }
+
+    pub fn get_tower_mut(&mut self, id: TowerId) -> Option<&mut Box<dyn Tower>> {
+        self.towers.iter_mut().find(|tower| tower.get_id() == id)
+    }
+
+    /// Ends the build phase, towers placed before are no longer sold for a
+    /// full refund.
+    pub fn start_wave(&mut self) {
+        self.build_phase = false;
+        self.refundable.clear();
+    }
+
+    /// Starts a build phase, towers placed from now on are sold for a full
+    /// refund until the next wave starts.
+    pub fn start_build_phase(&mut self) {
+        self.build_phase = true;
+    }
+
+    /// Whether any monster on the board is still alive.
+    pub fn has_live_monsters(&self) -> bool {
+        self.monster_views.iter().any(|monster_view| {
+            monster_view.get_monster().get_current_state() != MonsterState::Dead
+        })
+    }
+
+    /// Adds a monster to the board, giving it a new id.
+    pub fn add_monster(&mut self, mut monster_view: Box<dyn MonsterView>) {
+        let monster = monster_view.get_monster_mut();
+        monster.set_id(self.next_monster_id);
+        self.next_monster_id += 1;
+
+        self.monster_views.push(monster_view);
+    }
+
+    /// Removes the tower with the given id from the board, freeing its
+    /// block.
+    pub fn remove_tower(&mut self, id: TowerId) -> Option<Box<dyn Tower>> {
+        let index = self.towers.iter().position(|tower| tower.get_id() == id)?;
+        let tower = self.towers.remove(index);
+        self.refundable.retain(|&refundable| refundable != id);
+
+        self.grid
+            .set(to_block(tower.get_block_position()), Occupant::Empty);
+        self.recompute_routes();
+
+        Some(tower)
+    }
+
+    /// Gold refunded when selling the tower with the given id.
+    pub fn sell_value(&self, id: TowerId) -> Option<u32> {
+        let invested = self.get_tower(id)?.get_invested_gold();
+        if self.refundable.contains(&id) {
+            return Some(invested);
+        }
+        Some((invested as f32 * self.sell_refund).floor() as u32)
+    }
+
+    /// Removes the tower with the given id from the board, returning the
+    /// gold refunded for it.
+    pub fn sell_tower(&mut self, id: TowerId) -> Option<u32> {
+        let refund = self.sell_value(id)?;
+        self.remove_tower(id)?;
+        Some(refund)
+    }
+
+    /// Deals damage to the tower with the given id, destroying it if it has
+    /// no health left. Returns whether the tower was destroyed.
+    pub fn damage_tower(&mut self, id: TowerId, damage: f32) -> bool {
+        let tower = match self.get_tower_mut(id) {
+            Some(tower) => tower,
+            None => return false,
+        };
+
+        tower.take_damage(damage);
+        if tower.get_health() > 0.0 {
+            return false;
+        }
+
+        info!("damage_tower: tower {} destroyed.", id);
+        self.remove_tower(id).is_some()
+    }
+
+    /// Gives every monster the combined effect of the auras of the other
+    /// monsters around it. Dead monsters don't give auras.
+    pub fn update_auras(&mut self) {
+        let auras: Vec<(usize, [f32; 2], Aura)> = self
+            .monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .enumerate()
+            .filter(|(_, monster)| monster.get_current_state() != MonsterState::Dead)
+            .flat_map(|(index, monster)| {
+                let position = monster.get_center_pos_abs();
+                monster
+                    .get_auras()
+                    .iter()
+                    .map(move |&aura| (index, position, aura))
+            })
+            .collect();
+
+        for (index, monster_view) in self.monster_views.iter_mut().enumerate() {
+            let monster = monster_view.get_monster_mut();
+            let effects = AuraEffects::collect(
+                monster.get_center_pos_abs(),
+                auras
+                    .iter()
+                    .filter(|(source, _, _)| *source != index)
+                    .map(|(_, position, aura)| (*position, aura)),
+            );
+            monster.set_aura_effects(effects);
+        }
+    }
+
+    /// Marks every stealthy monster within the detection range of any tower
+    /// as detected, and all others as undetected.
+    pub fn update_detection(&mut self) {
+        let detectors: Vec<([f32; 2], f32)> = self
+            .towers
+            .iter()
+            .filter_map(|tower| {
+                let range = tower.detection_range()?;
+                Some((block_center(tower.get_block_position()), range))
+            })
+            .collect();
+
+        for monster_view in self.monster_views.iter_mut() {
+            let monster = monster_view.get_monster_mut();
+            if !monster.is_stealthy() {
+                continue;
+            }
+
+            let position = monster.get_center_pos_abs();
+            let detected = detectors
+                .iter()
+                .any(|&(center, range)| is_within(center, position, range));
+            monster.set_detected(detected);
+        }
+    }
+
+    /// Moves all projectiles. Projectiles reaching their destination hit
+    /// the monsters there and are removed.
+    pub fn update_projectiles(&mut self, elapsed: f32, asset_manager: &mut AssetManager) {
+        let mut projectiles = std::mem::take(&mut self.projectiles);
+
+        projectiles.retain_mut(|projectile| {
+            let target_position = self
+                .monster_views
+                .iter()
+                .map(|monster_view| monster_view.get_monster())
+                .find(|monster| {
+                    monster.get_id() == projectile.target
+                        && monster.get_current_state() != MonsterState::Dead
+                })
+                .map(|monster| monster.get_center_pos_abs());
+
+            if !projectile.advance(elapsed, target_position) {
+                return true;
+            }
+            self.projectile_impact(projectile, asset_manager);
+            false
+        });
+
+        self.projectiles = projectiles;
+    }
+
+    /// Deals the damage of a projectile at its current position, to the
+    /// monster hit directly and to all monsters within its splash.
+    fn projectile_impact(&mut self, projectile: &Projectile, asset_manager: &mut AssetManager) {
+        let candidates: Vec<(usize, &dyn Monster)> = self
+            .monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .enumerate()
+            .filter(|(_, monster)| monster.get_current_state() != MonsterState::Dead)
+            .collect();
+
+        let hit = projectile.find_hit(&candidates);
+        let splashed: Vec<(usize, f32)> = match projectile.stats.splash {
+            Some(splash) => candidates
+                .iter()
+                .filter(|&&(index, monster)| Some(index) != hit && projectile.can_hit(monster))
+                .map(|&(index, monster)| {
+                    (index, splash.multiplier(projectile.distance_to(monster)))
+                })
+                .filter(|&(_, multiplier)| multiplier > 0.0)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        match hit {
+            Some(index) => {
+                let monster = self.monster_views[index].get_monster_mut();
+                monster.recieve_damage(projectile.damage, &mut self.pickups, asset_manager);
+                for &effect in projectile.on_hit.iter() {
+                    monster.apply_status_effect(effect);
+                }
+            }
+            None => debug!(
+                "projectile_impact: projectile at {:?} missed.",
+                projectile.position
+            ),
+        }
+
+        for (index, multiplier) in splashed {
+            self.monster_views[index].get_monster_mut().recieve_damage(
+                projectile.damage.scaled(multiplier),
+                &mut self.pickups,
+                asset_manager,
+            );
+        }
     }
+
+    /// Disables all towers whose center is within `range` pixels of
+    /// `position_abs` for `duration` seconds.
+    pub fn disable_towers(&mut self, position_abs: [f32; 2], range: f32, duration: f32) {
+        for tower in self.towers.iter_mut() {
+            if is_within(
+                block_center(tower.get_block_position()),
+                position_abs,
+                range,
+            ) {
+                debug!("disable_towers: disabling tower {}.", tower.get_id());
+                tower.disable(duration);
+            }
+        }
+    }
+}
+
+fn to_position(block: [i32; 2]) -> [f32; 2] {
+    [block[0] as f32, block[1] as f32]
+}
+
+fn to_block(position: [f32; 2]) -> [i32; 2] {
+    [position[0] as i32, position[1] as i32]
+}
+
+/// Absolute (pixel) position of the center of a block.
+fn block_center(block: [f32; 2]) -> [f32; 2] {
+    [
+        block[0] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+        block[1] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+    ]
+}
+
+/// Whether two absolute (pixel) positions are at most `range` apart.
+fn is_within(a: [f32; 2], b: [f32; 2], range: f32) -> bool {
+    let dx = a[0] - b[0];
+    let dy = a[1] - b[1];
+    dx * dx + dy * dy <= range * range
+}
+
+/// Block containing the given absolute (pixel) position.
+fn block_of(position_abs: [f32; 2]) -> [i32; 2] {
+    [
+        (position_abs[0] / BLOCK_SIZE).floor() as i32,
+        (position_abs[1] / BLOCK_SIZE).floor() as i32,
+    ]
+}
+
+fn is_on_board(position: [i32; 2]) -> bool {
+    position[0] >= 0 && position[0] < BOARD_WIDTH && position[1] >= 0 && position[1] < BOARD_HEIGHT
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{towers::basic_tower::BasicTower, towers::tower::Tower, Board};
+    use crate::game_components::{
+        towers::{GenericTower, Tower, TowerRegistry},
+        Board, Occupant, PickupKind, Terrain, BLOCK_SIZE,
+    };
+    use crate::level_system::MapDefinition;
 
-    fn _check_towers_in_order(towers: &Vec<Box<dyn Tower>>) -> bool {
+    fn _check_towers_in_order(towers: &[Box<dyn Tower>]) -> bool {
         for i in 0..towers.len() - 1 {
             if towers[i].get_block_position()[1] > towers[i + 1].get_block_position()[1] {
                 return false;
             }
         }
-        return true;
+        true
+    }
+
+    fn towers() -> TowerRegistry {
+        TowerRegistry::from_ron_str(include_str!("../../resources/towers/towers.ron")).unwrap()
     }
 
     fn _fill_tower_positions(board: &mut Board, tower_y_positions: Vec<f32>) {
+        let basic = towers().get("Basic").unwrap();
         for y in tower_y_positions {
-            board.add_tower(Box::new(BasicTower::new([0.0, y])));
+            board.add_tower(Box::new(GenericTower::new(basic.clone(), [0.0, y])));
         }
     }
 
+    #[test]
+    fn sell_towers() {
+        let mut board = Board::generate(0, 0);
+        _fill_tower_positions(&mut board, vec![3.0, 5.0]);
+        let first = board.towers[0].get_id();
+        let second = board.towers[1].get_id();
+        assert!(board.towers[1].upgrade(0));
+
+        // Placed during the current build phase.
+        assert_eq!(board.sell_tower(first), Some(10));
+        assert_eq!(board.grid.get([0, 3]), Some(Occupant::Empty));
+        assert_eq!(board.sell_tower(first), None);
+
+        // A wave started since, towers placed during it aren't refunded in
+        // full either.
+        board.start_wave();
+        _fill_tower_positions(&mut board, vec![7.0]);
+        let third = board.towers[1].get_id();
+        assert_eq!(board.sell_value(third), Some(7));
+        assert_eq!(board.sell_value(second), Some(17));
+        assert_eq!(board.sell_tower(second), Some(17));
+
+        // Until the next build phase.
+        board.start_build_phase();
+        _fill_tower_positions(&mut board, vec![9.0]);
+        let fourth = board.towers[1].get_id();
+        assert_eq!(board.sell_value(fourth), Some(10));
+        assert_eq!(board.sell_value(third), Some(7));
+    }
+
     #[test]
     fn tower_order_0_1_2() {
         let mut board = Board::generate(0, 0);
@@ -272,4 +932,260 @@ mod tests {
         // Check sorted on y position.
         assert!(_check_towers_in_order(&board.towers));
     }
+
+    #[test]
+    fn destroyed_tower_is_removed() {
+        let mut board = Board::generate(0, 0);
+        _fill_tower_positions(&mut board, vec![3.0, 5.0]);
+        let id = board.towers[0].get_id();
+        let health = board.towers[0].get_max_health();
+
+        assert!(!board.damage_tower(id, health / 2.0));
+        assert_eq!(board.grid.get([0, 3]), Some(Occupant::Tower(id)));
+        let click_position = [BLOCK_SIZE / 2.0, 3.5 * BLOCK_SIZE];
+        assert_eq!(board.tower_at(click_position), Some(id));
+
+        assert!(board.damage_tower(id, health / 2.0));
+        assert_eq!(board.towers.len(), 1);
+        assert_eq!(board.grid.get([0, 3]), Some(Occupant::Empty));
+        assert_eq!(board.tower_at(click_position), None);
+        assert!(board.remove_tower(id).is_none());
+    }
+
+    #[test]
+    fn spawn_position_across_route() {
+        let route = vec![[2.0, 3.0], [2.0, 4.0]];
+        let center = [2.5 * BLOCK_SIZE, 3.5 * BLOCK_SIZE];
+
+        assert_eq!(Board::spawn_position(&route, 0.0), Some(center));
+        // Walking down, sideways is along x.
+        assert_eq!(
+            Board::spawn_position(&route, 5.0),
+            Some([center[0] - 5.0, center[1]])
+        );
+        assert_eq!(
+            Board::spawn_position(&route[..1], 5.0),
+            Some([center[0], center[1] + 5.0])
+        );
+        assert_eq!(Board::spawn_position(&[], 5.0), None);
+    }
+
+    #[test]
+    fn pick_route_per_entrance() {
+        let map =
+            MapDefinition::from_ron_str(include_str!("../../resources/maps/level_2.ron")).unwrap();
+        let board = Board::from_map(&map).unwrap();
+
+        let spawn_points = board.spawn_points();
+        assert_eq!(spawn_points, vec![[0.0, 1.0], [16.0, 0.0]]);
+
+        for (entrance, spawn_point) in spawn_points.iter().enumerate() {
+            let route = board.pick_route(entrance).unwrap();
+            assert_eq!(route[0], *spawn_point);
+        }
+        assert!(board.pick_route(spawn_points.len()).is_none());
+
+        // Shared blocks are only drawn once.
+        let positions: Vec<[f32; 2]> = board.path_blocks.iter().map(|b| b.position).collect();
+        for (i, position) in positions.iter().enumerate() {
+            assert!(!positions[..i].contains(position));
+        }
+    }
+
+    #[test]
+    fn flight_route_ignores_path() {
+        let map =
+            MapDefinition::from_ron_str(include_str!("../../resources/maps/level_2.ron")).unwrap();
+        let board = Board::from_map(&map).unwrap();
+
+        let route = board.flight_route(1).unwrap();
+        assert_eq!(route.len(), 2);
+        assert_eq!(route[0], board.spawn_points()[1]);
+        assert_eq!(
+            route[1],
+            [board.base.position[0] + 0.5, board.base.position[1] + 0.5]
+        );
+        assert!(board.flight_route(2).is_none());
+    }
+
+    fn click_position(block: [f32; 2]) -> [f32; 2] {
+        [
+            block[0] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+            block[1] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+        ]
+    }
+
+    #[test]
+    fn terrain_limits_tower_placement() {
+        let towers = towers();
+        let basic = towers.get("Basic").unwrap();
+        let ninja = towers.get("Ninja").unwrap();
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1), (1, 1)]], base: (2, 0), \
+              terrain: [(terrain: MudPath, from: (0, 1), to: (1, 1)), \
+                        (terrain: Water, from: (5, 5), to: (5, 5)), \
+                        (terrain: Rock, from: (6, 6), to: (6, 6)), \
+                        (terrain: HighGround, from: (7, 7), to: (7, 7))], \
+              wave_schedule: \"/waves/test.ron\")",
+        )
+        .unwrap();
+        let board = Board::from_map(&map).unwrap();
+
+        assert_eq!(board.grid.terrain([0, 0]), Terrain::Path);
+        assert_eq!(board.grid.terrain([0, 1]), Terrain::MudPath);
+        assert_eq!(board.terrain_blocks.len(), 3);
+
+        let water = click_position([5.0, 5.0]);
+        assert!(!board.can_place_tower(water, &basic));
+        assert!(board.can_place_tower(water, &ninja));
+
+        let rock = click_position([6.0, 6.0]);
+        assert!(!board.can_place_tower(rock, &basic));
+        assert!(!board.can_place_tower(rock, &ninja));
+
+        let high_ground = click_position([7.0, 7.0]);
+        assert!(board.can_place_tower(high_ground, &basic));
+    }
+
+    #[test]
+    fn clear_obstacle_frees_block_and_drops_gold() {
+        let towers = towers();
+        let basic = towers.get("Basic").unwrap();
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1), (1, 1)]], base: (2, 0), \
+              obstacles: [(kind: Tree, position: (5, 5), gold_drop: 20), \
+                          (kind: Rock, position: (6, 5), clear_cost: Some(50))], \
+              wave_schedule: \"/waves/test.ron\")",
+        )
+        .unwrap();
+        let mut board = Board::from_map(&map).unwrap();
+
+        let tree = click_position([5.0, 5.0]);
+        let rock = click_position([6.0, 5.0]);
+        assert!(!board.can_place_tower(tree, &basic));
+        assert_eq!(board.obstacle_at(tree).unwrap().clear_cost, 15);
+        assert_eq!(board.obstacle_at(rock).unwrap().clear_cost, 50);
+
+        assert!(board.clear_obstacle(tree).is_some());
+        assert!(board.obstacle_at(tree).is_none());
+        assert_eq!(board.grid.get([5, 5]), Some(Occupant::Empty));
+        assert!(board.can_place_tower(tree, &basic));
+        assert_eq!(board.pickups.len(), 1);
+        assert_eq!(board.pickups[0].kind, PickupKind::Gold(20));
+
+        // Rocks without gold_drop leave nothing behind.
+        assert!(board.clear_obstacle(rock).is_some());
+        assert_eq!(board.pickups.len(), 1);
+        assert!(board.clear_obstacle(rock).is_none());
+    }
+
+    #[test]
+    fn maze_routes_through_unbuildable_cells() {
+        // A full column of unbuildable cells between the entrance and base.
+        let map = MapDefinition::from_ron_str(
+            "(mode: Maze, width: 5, height: 5, entrances: [(0, 2)], base: (3, 0), \
+              unbuildable: [(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)], \
+              wave_schedule: \"/waves/test.ron\")",
+        )
+        .unwrap();
+        let board = Board::from_map(&map).unwrap();
+        let basic = towers().get("Basic").unwrap();
+
+        assert!(board.routes[0].contains(&[1.0, 2.0]));
+        assert_eq!(board.grid.get([1, 2]), Some(Occupant::Unbuildable));
+        assert!(!board.can_place_tower(click_position([1.0, 2.0]), &basic));
+    }
+
+    #[test]
+    fn maze_towers_reroute_and_may_not_block() {
+        let towers = towers();
+        let basic = towers.get("Basic").unwrap();
+        let map = MapDefinition::from_ron_str(
+            "(mode: Maze, width: 5, height: 5, entrances: [(0, 2)], base: (3, 0), \
+              wave_schedule: \"/waves/test.ron\")",
+        )
+        .unwrap();
+        let mut board = Board::from_map(&map).unwrap();
+        assert_eq!(board.routes[0].len(), 4);
+
+        // Build a wall along x = 1, leaving a gap at the bottom.
+        for y in 0..4 {
+            let block = [1.0, y as f32];
+            assert!(board.can_place_tower(click_position(block), &basic));
+            board.add_tower(Box::new(GenericTower::new(basic.clone(), block)));
+        }
+
+        // Monsters now have to go through the gap.
+        assert!(board.routes[0].contains(&[1.0, 4.0]));
+        for position in board.routes[0].iter() {
+            let is_tower = board
+                .towers
+                .iter()
+                .any(|tower| tower.get_block_position() == *position);
+            assert!(!is_tower, "route goes through tower at {:?}", position);
+        }
+
+        // Closing the gap would block the route completely.
+        assert!(!board.can_place_tower(click_position([1.0, 4.0]), &basic));
+    }
+
+    #[test]
+    fn generate_same_seed_same_board() {
+        let first = Board::generate(42, 40);
+        let second = Board::generate(42, 40);
+
+        let first_path: Vec<[f32; 2]> = first.path_blocks.iter().map(|b| b.position).collect();
+        let second_path: Vec<[f32; 2]> = second.path_blocks.iter().map(|b| b.position).collect();
+
+        assert_eq!(first_path, second_path);
+        assert_eq!(first.base.position, second.base.position);
+    }
+
+    #[test]
+    fn generate_path_is_contiguous_and_non_intersecting() {
+        for seed in 0..20 {
+            let board = Board::generate(seed, 40);
+            assert_eq!(board.path_blocks.len(), 40);
+
+            for pair in board.path_blocks.windows(2) {
+                let dx = (pair[0].position[0] - pair[1].position[0]).abs();
+                let dy = (pair[0].position[1] - pair[1].position[1]).abs();
+                assert_eq!(dx + dy, 1.0, "seed {} path not contiguous", seed);
+            }
+
+            for (i, block) in board.path_blocks.iter().enumerate() {
+                for other in board.path_blocks.iter().skip(i + 1) {
+                    assert_ne!(
+                        block.position, other.position,
+                        "seed {} path overlaps",
+                        seed
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generate_base_at_end_of_path() {
+        for seed in 0..20 {
+            let board = Board::generate(seed, 30);
+            let last = board.path_blocks.last().unwrap().position;
+            let base = board.base.position;
+
+            let covered = [
+                base,
+                [base[0] + 1.0, base[1]],
+                [base[0], base[1] + 1.0],
+                [base[0] + 1.0, base[1] + 1.0],
+            ];
+
+            // Base must be next to the last block without covering the path.
+            assert!(covered
+                .iter()
+                .any(|b| { (b[0] - last[0]).abs() + (b[1] - last[1]).abs() == 1.0 }));
+            for block in board.path_blocks.iter() {
+                assert!(!covered.contains(&block.position));
+            }
+        }
+    }
 }
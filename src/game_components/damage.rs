@@ -0,0 +1,139 @@
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum DamageType {
+    /// Reduced by armor and physical resistance.
+    Physical,
+    /// Ignores armor, reduced by magic resistance.
+    Magic,
+    /// Ignores armor, reduced by pierce resistance.
+    Pierce,
+    /// Ignores armor and resistances.
+    True,
+}
+
+/// An amount of typed damage, e.g. a single tower hit.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct Damage {
+    pub amount: f32,
+    pub damage_type: DamageType,
+}
+
+impl Damage {
+    pub const fn new(amount: f32, damage_type: DamageType) -> Damage {
+        Damage {
+            amount,
+            damage_type,
+        }
+    }
+
+    /// The same damage with its amount multiplied, e.g. by a damage buff.
+    pub fn scaled(self, multiplier: f32) -> Damage {
+        Damage::new(self.amount * multiplier, self.damage_type)
+    }
+}
+
+/// Armor and resistances of a monster, used to resolve the damage it
+/// actually takes from a hit.
+///
+/// Example:
+/// ```ron
+/// (armor: 3.0, resistances: {Magic: 0.5, Pierce: -0.25})
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct Defenses {
+    /// Flat amount of physical damage blocked from every hit.
+    #[serde(default)]
+    pub armor: f32,
+    /// Fraction of the damage of each type that is blocked, 1.0 meaning
+    /// immune. Negative values are weaknesses.
+    #[serde(default)]
+    pub resistances: HashMap<DamageType, f32>,
+}
+
+impl Defenses {
+    /// Final damage taken from a hit, `armor_reduction` is subtracted from
+    /// the armor first (e.g. by armor break effects).
+    pub fn resolve(&self, damage: Damage, armor_reduction: f32) -> f32 {
+        if damage.damage_type == DamageType::True {
+            return damage.amount.max(0.0);
+        }
+
+        let mut amount = damage.amount;
+        if damage.damage_type == DamageType::Physical {
+            amount -= (self.armor - armor_reduction).max(0.0);
+        }
+
+        let resistance = self
+            .resistances
+            .get(&damage.damage_type)
+            .copied()
+            .unwrap_or(0.0)
+            .min(1.0);
+
+        (amount * (1.0 - resistance)).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::damage::{Damage, DamageType, Defenses};
+
+    fn armored() -> Defenses {
+        ron::de::from_str("(armor: 4.0, resistances: {Magic: 0.5, Pierce: -0.5, Physical: 0.25})")
+            .unwrap()
+    }
+
+    #[test]
+    fn no_defenses() {
+        let defenses = Defenses::default();
+        for &damage_type in [
+            DamageType::Physical,
+            DamageType::Magic,
+            DamageType::Pierce,
+            DamageType::True,
+        ]
+        .iter()
+        {
+            assert_eq!(defenses.resolve(Damage::new(10.0, damage_type), 0.0), 10.0);
+        }
+    }
+
+    #[test]
+    fn armor_then_resistance() {
+        let defenses = armored();
+        assert_eq!(
+            defenses.resolve(Damage::new(12.0, DamageType::Physical), 0.0),
+            6.0
+        );
+        // Armor can block a hit completely.
+        assert_eq!(
+            defenses.resolve(Damage::new(3.0, DamageType::Physical), 0.0),
+            0.0
+        );
+        // Armor break.
+        assert_eq!(
+            defenses.resolve(Damage::new(12.0, DamageType::Physical), 10.0),
+            9.0
+        );
+    }
+
+    #[test]
+    fn resistances_and_weaknesses() {
+        let defenses = armored();
+        assert_eq!(
+            defenses.resolve(Damage::new(10.0, DamageType::Magic), 0.0),
+            5.0
+        );
+        assert_eq!(
+            defenses.resolve(Damage::new(10.0, DamageType::Pierce), 0.0),
+            15.0
+        );
+        assert_eq!(
+            defenses.resolve(Damage::new(10.0, DamageType::True), 0.0),
+            10.0
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::game_components::{terrain::Terrain, towers::TowerId, BLOCK_SIZE};
+
+/// What is occupying a block of the board.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Occupant {
+    Empty,
+    Path,
+    Tower(TowerId),
+    Base,
+    /// Cell the map marks as unbuildable. Monsters can still walk through
+    /// it.
+    Unbuildable,
+    /// An obstacle such as a tree that can be cleared.
+    Obstacle,
+}
+
+/// Per block occupancy index of the board, giving constant time lookups of
+/// what is placed where and of the terrain underneath.
+pub struct Grid {
+    pub width: i32,
+    pub height: i32,
+    cells: Vec<Occupant>,
+    terrain: Vec<Terrain>,
+}
+
+impl Grid {
+    pub fn new(width: i32, height: i32) -> Grid {
+        let size = (width.max(0) * height.max(0)) as usize;
+        Grid {
+            width,
+            height,
+            cells: vec![Occupant::Empty; size],
+            terrain: vec![Terrain::default(); size],
+        }
+    }
+
+    pub fn is_in_bounds(&self, block: [i32; 2]) -> bool {
+        block[0] >= 0 && block[0] < self.width && block[1] >= 0 && block[1] < self.height
+    }
+
+    /// Occupant of the block, `None` if the block is outside of the grid.
+    pub fn get(&self, block: [i32; 2]) -> Option<Occupant> {
+        if !self.is_in_bounds(block) {
+            return None;
+        }
+        Some(self.cells[self.index(block)])
+    }
+
+    /// Sets the occupant of the block, blocks outside of the grid are
+    /// ignored.
+    pub fn set(&mut self, block: [i32; 2], occupant: Occupant) {
+        if self.is_in_bounds(block) {
+            let index = self.index(block);
+            self.cells[index] = occupant;
+        }
+    }
+
+    /// Whether the block is inside the grid and nothing occupies it.
+    pub fn is_free(&self, block: [i32; 2]) -> bool {
+        self.get(block) == Some(Occupant::Empty)
+    }
+
+    /// Whether the occupant of the block lets monsters through, regardless
+    /// of the terrain underneath.
+    pub fn is_passable(&self, block: [i32; 2]) -> bool {
+        matches!(
+            self.get(block),
+            Some(Occupant::Empty) | Some(Occupant::Path) | Some(Occupant::Unbuildable)
+        )
+    }
+
+    /// Terrain of the block, blocks outside of the grid count as rock.
+    pub fn terrain(&self, block: [i32; 2]) -> Terrain {
+        if !self.is_in_bounds(block) {
+            return Terrain::Rock;
+        }
+        self.terrain[self.index(block)]
+    }
+
+    /// Terrain of the block containing the given absolute (pixel) position.
+    pub fn terrain_at(&self, position_abs: [f32; 2]) -> Terrain {
+        self.terrain([
+            (position_abs[0] / BLOCK_SIZE).floor() as i32,
+            (position_abs[1] / BLOCK_SIZE).floor() as i32,
+        ])
+    }
+
+    /// Sets the terrain of the block, blocks outside of the grid are
+    /// ignored.
+    pub fn set_terrain(&mut self, block: [i32; 2], terrain: Terrain) {
+        if self.is_in_bounds(block) {
+            let index = self.index(block);
+            self.terrain[index] = terrain;
+        }
+    }
+
+    /// The horizontal and vertical neighbors of the block inside the grid.
+    pub fn neighbors(&self, block: [i32; 2]) -> Vec<[i32; 2]> {
+        adjacent_blocks(block)
+            .iter()
+            .copied()
+            .filter(|&neighbor| self.is_in_bounds(neighbor))
+            .collect()
+    }
+
+    /// The tower whose block center is closest to the given absolute (pixel)
+    /// position, if any is at most `range` pixels away.
+    pub fn nearest_tower(&self, position_abs: [f32; 2], range: f32) -> Option<TowerId> {
+        let reach = (range / BLOCK_SIZE).ceil() as i32 + 1;
+        let center = [
+            (position_abs[0] / BLOCK_SIZE).floor() as i32,
+            (position_abs[1] / BLOCK_SIZE).floor() as i32,
+        ];
+
+        let mut nearest = None;
+        for y in center[1] - reach..=center[1] + reach {
+            for x in center[0] - reach..=center[0] + reach {
+                let id = match self.get([x, y]) {
+                    Some(Occupant::Tower(id)) => id,
+                    _ => continue,
+                };
+
+                let dx = x as f32 * BLOCK_SIZE + BLOCK_SIZE / 2.0 - position_abs[0];
+                let dy = y as f32 * BLOCK_SIZE + BLOCK_SIZE / 2.0 - position_abs[1];
+                let distance = (dx * dx + dy * dy).sqrt();
+                if distance <= range && nearest.is_none_or(|(_, d)| distance < d) {
+                    nearest = Some((id, distance));
+                }
+            }
+        }
+
+        nearest.map(|(id, _)| id)
+    }
+
+    fn index(&self, block: [i32; 2]) -> usize {
+        (block[1] * self.width + block[0]) as usize
+    }
+}
+
+/// The four horizontal and vertical neighbors of a block, regardless of
+/// whether they are on the board.
+pub fn adjacent_blocks(block: [i32; 2]) -> [[i32; 2]; 4] {
+    let [x, y] = block;
+    [[x + 1, y], [x - 1, y], [x, y + 1], [x, y - 1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        grid::{Grid, Occupant},
+        terrain::Terrain,
+        BLOCK_SIZE,
+    };
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = Grid::new(4, 3);
+        assert_eq!(grid.get([3, 2]), Some(Occupant::Empty));
+        assert_eq!(grid.get([4, 2]), None);
+        assert_eq!(grid.get([-1, 0]), None);
+
+        grid.set([3, 2], Occupant::Tower(7));
+        assert_eq!(grid.get([3, 2]), Some(Occupant::Tower(7)));
+        assert!(!grid.is_free([3, 2]));
+        assert!(grid.is_free([2, 2]));
+
+        grid.set([2, 2], Occupant::Unbuildable);
+        assert!(!grid.is_free([2, 2]));
+        assert!(grid.is_passable([2, 2]));
+        assert!(!grid.is_passable([3, 2]));
+    }
+
+    #[test]
+    fn neighbors_inside_grid() {
+        let grid = Grid::new(4, 3);
+        assert_eq!(grid.neighbors([0, 0]), vec![[1, 0], [0, 1]]);
+        assert_eq!(grid.neighbors([1, 1]).len(), 4);
+    }
+
+    #[test]
+    fn nearest_tower_in_range() {
+        let mut grid = Grid::new(10, 10);
+        grid.set([2, 2], Occupant::Tower(1));
+        grid.set([5, 2], Occupant::Tower(2));
+        let position = [3.5 * BLOCK_SIZE, 2.5 * BLOCK_SIZE];
+
+        assert_eq!(grid.nearest_tower(position, 0.5 * BLOCK_SIZE), None);
+        assert_eq!(grid.nearest_tower(position, BLOCK_SIZE), Some(1));
+        assert_eq!(grid.nearest_tower(position, 3.0 * BLOCK_SIZE), Some(1));
+
+        grid.set([2, 2], Occupant::Empty);
+        assert_eq!(grid.nearest_tower(position, 3.0 * BLOCK_SIZE), Some(2));
+    }
+
+    #[test]
+    fn terrain_layer() {
+        let mut grid = Grid::new(4, 3);
+        assert_eq!(grid.terrain([1, 1]), Terrain::Grass);
+        assert_eq!(grid.terrain([4, 0]), Terrain::Rock);
+
+        grid.set_terrain([1, 1], Terrain::MudPath);
+        assert_eq!(grid.terrain([1, 1]), Terrain::MudPath);
+        assert_eq!(
+            grid.terrain_at([1.5 * BLOCK_SIZE, 1.9 * BLOCK_SIZE]),
+            Terrain::MudPath
+        );
+        // Terrain is independent of what is placed on top of it.
+        assert!(grid.is_free([1, 1]));
+    }
+}
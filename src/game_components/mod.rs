@@ -1,8 +1,14 @@
 pub mod base;
 pub mod block;
 pub mod board;
-pub mod gold;
+pub mod damage;
+pub mod grid;
+pub mod obstacle;
+pub mod pathfinding;
+pub mod pickup;
 pub mod player;
+pub mod projectile;
+pub mod terrain;
 
 pub mod monsters;
 pub mod towers;
@@ -12,5 +18,11 @@ pub use self::base::Base;
 pub use self::block::Block;
 pub use self::block::BLOCK_SIZE;
 pub use self::board::Board;
-pub use self::gold::GoldPile;
+pub use self::board::BoardMode;
+pub use self::damage::{Damage, DamageType, Defenses};
+pub use self::grid::{Grid, Occupant};
+pub use self::obstacle::ObstacleDefinition;
+pub use self::pickup::{LootTable, Pickup, PickupKind};
 pub use self::player::Player;
+pub use self::projectile::Projectile;
+pub use self::terrain::{Terrain, TerrainArea};
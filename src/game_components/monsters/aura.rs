@@ -0,0 +1,112 @@
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum AuraKind {
+    /// Heals `strength` health per second.
+    Heal,
+    /// Increases movement speed by `strength` (0.3 = 30% faster).
+    Haste,
+    /// Reduces all damage taken by `strength` (0.3 = 30% less damage).
+    Shield,
+}
+
+/// Buff a monster gives to all other monsters within `range` pixels of it,
+/// for as long as they stay in range.
+///
+/// Example:
+/// ```ron
+/// (kind: Heal, strength: 5.0, range: 80.0)
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct Aura {
+    pub kind: AuraKind,
+    pub strength: f32,
+    pub range: f32,
+}
+
+/// The combined effect of all auras a monster is standing in. Auras of the
+/// same kind don't stack, only the strongest one counts.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct AuraEffects {
+    /// Health per second.
+    pub heal: f32,
+    pub haste: f32,
+    pub shield: f32,
+}
+
+impl AuraEffects {
+    /// Damage shields never make a monster immune.
+    pub const MAX_SHIELD: f32 = 0.9;
+
+    /// Combines the auras, given with the position of the monster giving
+    /// them, that reach `position`. Positions are absolute (pixels).
+    pub fn collect<'a>(
+        position: [f32; 2],
+        auras: impl Iterator<Item = ([f32; 2], &'a Aura)>,
+    ) -> AuraEffects {
+        let mut effects = AuraEffects::default();
+
+        for (source, aura) in auras {
+            let dx = source[0] - position[0];
+            let dy = source[1] - position[1];
+            if dx * dx + dy * dy > aura.range * aura.range {
+                continue;
+            }
+
+            let effect = match aura.kind {
+                AuraKind::Heal => &mut effects.heal,
+                AuraKind::Haste => &mut effects.haste,
+                AuraKind::Shield => &mut effects.shield,
+            };
+            *effect = effect.max(aura.strength);
+        }
+
+        effects
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        1.0 + self.haste
+    }
+
+    /// Multiplier of all damage taken.
+    pub fn damage_multiplier(&self) -> f32 {
+        1.0 - self.shield.clamp(0.0, AuraEffects::MAX_SHIELD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::monsters::aura::{Aura, AuraEffects, AuraKind};
+
+    fn aura(kind: AuraKind, strength: f32) -> Aura {
+        Aura {
+            kind,
+            strength,
+            range: 50.0,
+        }
+    }
+
+    #[test]
+    fn only_auras_in_range() {
+        let heal = aura(AuraKind::Heal, 5.0);
+        let haste = aura(AuraKind::Haste, 0.5);
+        let auras = vec![([0.0, 0.0], &heal), ([100.0, 0.0], &haste)];
+
+        let effects = AuraEffects::collect([0.0, 40.0], auras.into_iter());
+
+        assert_eq!(effects.heal, 5.0);
+        assert_eq!(effects.speed_multiplier(), 1.0);
+        assert_eq!(effects.damage_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn strongest_aura_of_each_kind() {
+        let weak = aura(AuraKind::Shield, 0.25);
+        let strong = aura(AuraKind::Shield, 0.5);
+        let auras = vec![([0.0, 0.0], &strong), ([0.0, 0.0], &weak)];
+
+        let effects = AuraEffects::collect([10.0, 10.0], auras.into_iter());
+
+        assert_eq!(effects.damage_multiplier(), 0.5);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::game_components::monsters::MonsterType;
+
+use serde::Deserialize;
+
+/// Something a boss does when it enters a new phase.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum BossAbility {
+    /// Multiplies the speed of the boss for `duration` seconds.
+    SpeedBurst { multiplier: f32, duration: f32 },
+    /// Spawns `count` monsters at the position of the boss, they follow the
+    /// rest of the route of the boss.
+    Summon { monster: MonsterType, count: u32 },
+    /// Makes the boss immune to damage for `duration` seconds.
+    Shield { duration: f32 },
+    /// Stops all towers within `range` pixels of the boss from attacking for
+    /// `duration` seconds.
+    DisableTowers { range: f32, duration: f32 },
+}
+
+/// Entered once the health of the boss drops to `health_threshold` (a
+/// fraction of its full health) or below.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BossPhase {
+    pub health_threshold: f32,
+    pub abilities: Vec<BossAbility>,
+}
+
+/// Makes a monster a boss, which is shown with a large health bar and
+/// changes behavior at each of its phases.
+///
+/// Example:
+/// ```ron
+/// (
+///     phases: [
+///         (health_threshold: 0.5, abilities: [Summon(monster: "Chicken", count: 3)]),
+///         (health_threshold: 0.2, abilities: [SpeedBurst(multiplier: 2.0, duration: 3.0)]),
+///     ],
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct BossDefinition {
+    /// In order, each threshold lower than the one before.
+    pub phases: Vec<BossPhase>,
+}
+
+impl BossDefinition {
+    /// Whether the phase thresholds are fractions between 0 and 1, in
+    /// descending order.
+    pub fn has_valid_phases(&self) -> bool {
+        let mut previous = 1.0;
+        for phase in self.phases.iter() {
+            if phase.health_threshold <= 0.0 || phase.health_threshold >= previous {
+                return false;
+            }
+            previous = phase.health_threshold;
+        }
+        true
+    }
+
+    /// Names of all monsters the boss can summon.
+    pub fn summoned_monsters(&self) -> impl Iterator<Item = &MonsterType> {
+        self.phases
+            .iter()
+            .flat_map(|phase| phase.abilities.iter())
+            .filter_map(|ability| match ability {
+                BossAbility::Summon { monster, .. } => Some(monster),
+                _ => None,
+            })
+    }
+}
@@ -0,0 +1,646 @@
+use crate::{
+    asset_system::AssetManager,
+    game_components::{
+        monsters::{
+            AttackTarget, Aura, AuraEffects, BossAbility, DeathEffect, Monster, MonsterDefinition,
+            MonsterEvent, MonsterId, MonsterState, MovementMode, PathFollower, StatusEffect,
+            StatusEffects,
+        },
+        Damage, Grid, Pickup, Player,
+    },
+    utils::Direction,
+};
+
+use ggez::audio::SoundSource;
+
+use rand::*;
+
+use std::rc::Rc;
+
+/// Monster whose properties all come from its `MonsterDefinition`.
+pub struct GenericMonster {
+    pub id: MonsterId,
+    pub definition: Rc<MonsterDefinition>,
+    /// Walks the route ending next to the base, tracks the center of the
+    /// monster.
+    pub path_follower: PathFollower,
+    pub speed: f32,
+    pub health: f32,
+    pub status_effects: StatusEffects,
+    /// Buffs from the auras of nearby monsters.
+    pub aura_effects: AuraEffects,
+    pub state: MonsterState,
+    pub direction: Direction,
+    /// Whether a tower with detection can see the monster, only matters for
+    /// stealthy monsters.
+    pub detected: bool,
+    /// Seconds left of the flash showing the monster was just hit.
+    pub hit_flash_time: f32,
+    /// Seconds until the monster can attack a tower again.
+    pub attack_cooldown: f32,
+    /// Index of the next boss phase to enter.
+    pub next_phase: usize,
+    /// Seconds left of the boss shield.
+    pub shield_time: f32,
+    pub speed_burst_multiplier: f32,
+    /// Seconds left of the boss speed burst.
+    pub speed_burst_time: f32,
+    events: Vec<MonsterEvent>,
+}
+
+impl GenericMonster {
+    /// Seconds the monster flashes after taking damage.
+    pub const HIT_FLASH_DURATION: f32 = 0.1;
+
+    /// Creates a monster centered at `position` (pixels), walking along
+    /// `route`.
+    pub fn new(
+        definition: Rc<MonsterDefinition>,
+        position: [f32; 2],
+        route: Vec<[f32; 2]>,
+    ) -> GenericMonster {
+        GenericMonster {
+            id: 0,
+            health: definition.health,
+            speed: definition.speed,
+            status_effects: StatusEffects::default(),
+            aura_effects: AuraEffects::default(),
+            path_follower: PathFollower::new(position, route),
+            definition,
+            state: MonsterState::Walking,
+            direction: Direction::Right,
+            detected: false,
+            hit_flash_time: 0.0,
+            attack_cooldown: 0.0,
+            next_phase: 0,
+            shield_time: 0.0,
+            speed_burst_multiplier: 1.0,
+            speed_burst_time: 0.0,
+            events: Vec::new(),
+        }
+    }
+
+    /// Walks the route `lateral_offset` pixels beside the lane center, see
+    /// `PathFollower::waypoint`.
+    pub fn with_lateral_offset(mut self, lateral_offset: f32) -> GenericMonster {
+        self.path_follower = self.path_follower.with_lateral_offset(lateral_offset);
+        self
+    }
+
+    /// Position of the top left corner of the monster, in pixels.
+    pub fn get_position(&self) -> [f32; 2] {
+        let center = self.path_follower.get_position();
+        [
+            center[0] - self.definition.size / 2.0,
+            center[1] - self.definition.size / 2.0,
+        ]
+    }
+
+    /// Lowers the health of the monster, after armor and other modifiers
+    /// have been applied. Drops the loot when the monster dies.
+    fn take_damage(
+        &mut self,
+        damage: f32,
+        pickups: &mut Vec<Pickup>,
+        asset_manager: &mut AssetManager,
+    ) {
+        if !self.lose_health(damage) {
+            return;
+        }
+
+        if let Some(assets) = asset_manager.monster_assets.get_mut(&self.definition.name) {
+            assets.hurt_sound.play().unwrap();
+        }
+        self.state = MonsterState::Dead;
+
+        let offset = 10.0;
+        let mut rng = rand::thread_rng();
+
+        let position = self.get_position();
+        for kind in self.definition.loot.roll(&mut rng) {
+            let pickup_position = [
+                position[0] + (rng.gen::<f32>() * offset - offset * 2.0),
+                position[1] + (rng.gen::<f32>() * offset - offset * 2.0),
+            ];
+            pickups.push(Pickup {
+                position: pickup_position,
+                kind,
+            });
+        }
+
+        self.on_death();
+    }
+
+    /// Lowers the health of the monster and flashes it if the hit did any
+    /// damage. Dead and shielded monsters are not affected. Returns whether
+    /// the hit killed the monster.
+    fn lose_health(&mut self, damage: f32) -> bool {
+        if self.state == MonsterState::Dead || self.state == MonsterState::Shielded {
+            // Already dead or immune, do nothing.
+            return false;
+        }
+
+        self.health -= damage;
+        if damage > 0.0 {
+            self.hit_flash_time = GenericMonster::HIT_FLASH_DURATION;
+        }
+        if self.health > 0.0 {
+            self.enter_phases();
+        }
+
+        self.health <= 0.0
+    }
+
+    /// Queues the death effects of the monster, e.g. splitting into smaller
+    /// monsters.
+    fn on_death(&mut self) {
+        for effect in self.definition.on_death.iter() {
+            match effect {
+                DeathEffect::Spawn { monster, count } => self.events.push(MonsterEvent::Summon {
+                    monster: monster.clone(),
+                    count: *count,
+                    position: self.get_center_pos_abs(),
+                    route: self.path_follower.remaining_route(),
+                }),
+            }
+        }
+    }
+
+    /// Enters every boss phase whose health threshold has been reached,
+    /// using the abilities of each.
+    fn enter_phases(&mut self) {
+        let definition = Rc::clone(&self.definition);
+        let phases = match &definition.boss {
+            Some(boss) => &boss.phases,
+            None => return,
+        };
+
+        while let Some(phase) = phases.get(self.next_phase) {
+            if self.health > phase.health_threshold * definition.health {
+                break;
+            }
+            info!(
+                "enter_phases: {} entering phase {}.",
+                definition.name, self.next_phase
+            );
+            self.next_phase += 1;
+            for ability in phase.abilities.iter() {
+                self.use_ability(ability);
+            }
+        }
+    }
+
+    fn use_ability(&mut self, ability: &BossAbility) {
+        match ability {
+            BossAbility::SpeedBurst {
+                multiplier,
+                duration,
+            } => {
+                self.speed_burst_multiplier = *multiplier;
+                self.speed_burst_time = *duration;
+            }
+            BossAbility::Summon { monster, count } => self.events.push(MonsterEvent::Summon {
+                monster: monster.clone(),
+                count: *count,
+                position: self.get_center_pos_abs(),
+                route: self.path_follower.remaining_route(),
+            }),
+            BossAbility::Shield { duration } => {
+                if self.state == MonsterState::Walking {
+                    self.state = MonsterState::Shielded;
+                    self.shield_time = *duration;
+                }
+            }
+            BossAbility::DisableTowers { range, duration } => {
+                self.events.push(MonsterEvent::DisableTowers {
+                    position: self.get_center_pos_abs(),
+                    range: *range,
+                    duration: *duration,
+                })
+            }
+        }
+    }
+
+    /// Heals the monster by the heal auras it is standing in, never above
+    /// its full health.
+    fn heal(&mut self, elapsed: f32) {
+        self.health = (self.health + self.aura_effects.heal * elapsed)
+            .min(self.definition.health)
+            .max(self.health);
+    }
+
+    /// Counts down the shield and speed burst of bosses.
+    fn update_abilities(&mut self, elapsed: f32) {
+        self.speed_burst_time = (self.speed_burst_time - elapsed).max(0.0);
+        self.shield_time = (self.shield_time - elapsed).max(0.0);
+
+        if self.state == MonsterState::Shielded && self.shield_time == 0.0 {
+            self.state = MonsterState::Walking;
+        }
+    }
+
+    /// Stops next to the closest tower in range and attacks it, monsters
+    /// without a tower attack ignore towers. Continues walking once there is
+    /// no tower left in range.
+    fn try_attacking_tower(&mut self, elapsed: f32, grid: &Grid) {
+        let attack = match self.definition.tower_attack {
+            Some(attack) => attack,
+            None => return,
+        };
+        self.attack_cooldown = (self.attack_cooldown - elapsed).max(0.0);
+
+        match self.state {
+            MonsterState::Walking | MonsterState::Attacking(AttackTarget::Tower(_)) => {}
+            _ => return,
+        }
+
+        let tower = match grid.nearest_tower(self.get_center_pos_abs(), attack.range) {
+            Some(tower) => tower,
+            None => {
+                self.state = MonsterState::Walking;
+                return;
+            }
+        };
+
+        self.state = MonsterState::Attacking(AttackTarget::Tower(tower));
+        if self.attack_cooldown == 0.0 {
+            self.events.push(MonsterEvent::AttackTower {
+                tower,
+                damage: attack.damage,
+            });
+            self.attack_cooldown = attack.cooldown;
+        }
+    }
+
+    /// Try moving along the route towards the base.
+    fn try_moving(&mut self, elapsed: f32, grid: &Grid) {
+        // Don't move unless in walking state.
+        if self.state != MonsterState::Walking && self.state != MonsterState::Shielded {
+            return;
+        }
+
+        // Check if at end of path.
+        if self.path_follower.is_finished() {
+            self.state = MonsterState::Attacking(AttackTarget::Base);
+            return; // No more moving to do.
+        }
+
+        // Terrain such as mud and status effects such as slows and stuns
+        // slow the monster down, flying monsters don't care about terrain.
+        let mut speed = self.get_speed();
+        if !self.is_flying() {
+            speed *= grid
+                .terrain_at(self.get_center_pos_abs())
+                .speed_multiplier();
+        }
+        self.path_follower.advance(speed * elapsed);
+
+        if self.path_follower.get_heading()[0] >= 0.0 {
+            self.direction = Direction::Right;
+        } else {
+            self.direction = Direction::Left;
+        }
+    }
+}
+
+impl Monster for GenericMonster {
+    fn get_id(&self) -> MonsterId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: MonsterId) {
+        self.id = id;
+    }
+
+    fn get_center_pos_abs(&self) -> [f32; 2] {
+        self.path_follower.get_position()
+    }
+
+    fn recieve_damage(
+        &mut self,
+        damage: Damage,
+        pickups: &mut Vec<Pickup>,
+        asset_manager: &mut AssetManager,
+    ) {
+        let damage = self
+            .definition
+            .defenses
+            .resolve(damage, self.status_effects.armor_reduction())
+            * self.status_effects.damage_multiplier()
+            * self.aura_effects.damage_multiplier();
+        self.take_damage(damage, pickups, asset_manager);
+    }
+
+    fn apply_status_effect(&mut self, effect: StatusEffect) {
+        if self.state == MonsterState::Dead {
+            return;
+        }
+        self.status_effects.apply(effect);
+    }
+
+    fn update_status_effects(
+        &mut self,
+        elapsed: f32,
+        pickups: &mut Vec<Pickup>,
+        asset_manager: &mut AssetManager,
+    ) {
+        for damage in self.status_effects.update(elapsed) {
+            if damage.amount > 0.0 {
+                self.recieve_damage(damage, pickups, asset_manager);
+            }
+        }
+    }
+
+    fn update(&mut self, elapsed: f32, player: &mut Player, grid: &Grid) {
+        self.hit_flash_time = (self.hit_flash_time - elapsed).max(0.0);
+
+        if self.state == MonsterState::Attacking(AttackTarget::Base) {
+            // Die and deal damange to the player.
+            player.health -= self.definition.leak_damage;
+            self.state = MonsterState::Dead;
+        }
+
+        // Don't do anything if dead.
+        if self.state == MonsterState::Dead {
+            return;
+        }
+
+        self.heal(elapsed);
+        self.update_abilities(elapsed);
+        self.try_attacking_tower(elapsed, grid);
+        self.try_moving(elapsed, grid);
+    }
+
+    fn get_current_state(&self) -> MonsterState {
+        self.state
+    }
+
+    fn set_route(&mut self, route: Vec<[f32; 2]>) {
+        self.path_follower.set_route(route);
+    }
+
+    fn distance_travelled(&self) -> f32 {
+        self.path_follower.distance_travelled()
+    }
+
+    fn distance_remaining(&self) -> f32 {
+        self.path_follower.distance_remaining()
+    }
+
+    fn is_flying(&self) -> bool {
+        self.definition.movement == MovementMode::Flying
+    }
+
+    fn is_stealthy(&self) -> bool {
+        self.definition.stealth
+    }
+
+    fn set_detected(&mut self, detected: bool) {
+        self.detected = detected;
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.is_stealthy() && !self.detected
+    }
+
+    fn get_speed(&self) -> f32 {
+        let speed_burst = if self.speed_burst_time > 0.0 {
+            self.speed_burst_multiplier
+        } else {
+            1.0
+        };
+        self.speed
+            * speed_burst
+            * self.status_effects.speed_multiplier()
+            * self.aura_effects.speed_multiplier()
+    }
+
+    fn get_name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn get_health(&self) -> f32 {
+        self.health
+    }
+
+    fn get_max_health(&self) -> f32 {
+        self.definition.health
+    }
+
+    fn is_boss(&self) -> bool {
+        self.definition.boss.is_some()
+    }
+
+    fn get_auras(&self) -> &[Aura] {
+        &self.definition.auras
+    }
+
+    fn set_aura_effects(&mut self, effects: AuraEffects) {
+        self.aura_effects = effects;
+    }
+
+    fn take_events(&mut self) -> Vec<MonsterEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        grid::{Grid, Occupant},
+        monsters::{
+            monster_definition::TEST_CHICK, AttackTarget, AuraEffects, GenericMonster, Monster,
+            MonsterEvent, MonsterRegistry, MonsterState,
+        },
+        Player, BLOCK_SIZE,
+    };
+
+    fn boss() -> GenericMonster {
+        let registry = MonsterRegistry::from_ron_str(&format!(
+            "[{}, \
+              (name: \"Hen\", speed: 50.0, health: 1000.0, size: 40.0, leak_damage: 20.0, \
+               loot: (gold: (100, 100)), walking_sprites: [\"/hen.png\"], hurt_sound: \"/cluck.ogg\", \
+               on_death: [Spawn(monster: \"Chick\", count: 2)], \
+               boss: Some((phases: [ \
+                   (health_threshold: 0.75, abilities: [Summon(monster: \"Chick\", count: 3)]), \
+                   (health_threshold: 0.5, abilities: [Shield(duration: 2.0), \
+                        SpeedBurst(multiplier: 2.0, duration: 1.0)]), \
+               ])))]",
+            TEST_CHICK
+        ))
+        .unwrap();
+
+        GenericMonster::new(
+            registry.get("Hen").unwrap(),
+            [BLOCK_SIZE / 2.0, BLOCK_SIZE / 2.0],
+            vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]],
+        )
+    }
+
+    #[test]
+    fn boss_enters_phases_at_thresholds() {
+        let mut boss = boss();
+        assert!(boss.is_boss());
+
+        boss.health = 800.0;
+        boss.enter_phases();
+        assert!(boss.take_events().is_empty());
+
+        // Both phases are entered by a single big hit.
+        boss.health = 400.0;
+        boss.enter_phases();
+        assert_eq!(boss.next_phase, 2);
+        match boss.take_events().as_slice() {
+            [MonsterEvent::Summon { monster, count, .. }] => {
+                assert_eq!(monster, "Chick");
+                assert_eq!(*count, 3);
+            }
+            events => panic!("unexpected events {:?}", events),
+        }
+        assert!(boss.take_events().is_empty());
+
+        assert_eq!(boss.get_current_state(), MonsterState::Shielded);
+        assert_eq!(boss.get_speed(), 100.0);
+    }
+
+    #[test]
+    fn boss_abilities_wear_off() {
+        let mut boss = boss();
+        boss.health = 400.0;
+        boss.enter_phases();
+
+        boss.update_abilities(1.5);
+        assert_eq!(boss.get_speed(), 50.0);
+        assert_eq!(boss.get_current_state(), MonsterState::Shielded);
+
+        boss.update_abilities(1.0);
+        assert_eq!(boss.get_current_state(), MonsterState::Walking);
+    }
+
+    #[test]
+    fn death_spawns_continue_route() {
+        let mut hen = boss();
+        hen.path_follower.advance(BLOCK_SIZE);
+
+        hen.on_death();
+
+        assert_eq!(
+            hen.take_events(),
+            vec![MonsterEvent::Summon {
+                monster: "Chick".to_string(),
+                count: 2,
+                position: hen.get_center_pos_abs(),
+                route: vec![[2.0, 0.0]],
+            }]
+        );
+    }
+
+    #[test]
+    fn heal_aura_up_to_full_health() {
+        let mut hen = boss();
+        hen.health = 990.0;
+        hen.set_aura_effects(AuraEffects {
+            heal: 8.0,
+            haste: 0.5,
+            shield: 0.0,
+        });
+
+        hen.heal(1.0);
+        assert_eq!(hen.get_health(), 998.0);
+        hen.heal(1.0);
+        assert_eq!(hen.get_health(), hen.get_max_health());
+        assert_eq!(hen.get_speed(), 75.0);
+    }
+
+    #[test]
+    fn hits_flash_until_update() {
+        let mut hen = boss();
+        let mut player = Player::new(100.0, 0);
+        let grid = Grid::new(5, 5);
+
+        assert!(!hen.lose_health(0.0));
+        assert_eq!(hen.hit_flash_time, 0.0);
+
+        assert!(!hen.lose_health(1.0));
+        assert_eq!(hen.hit_flash_time, GenericMonster::HIT_FLASH_DURATION);
+        hen.update(GenericMonster::HIT_FLASH_DURATION / 2.0, &mut player, &grid);
+        assert!(hen.hit_flash_time > 0.0);
+        hen.update(GenericMonster::HIT_FLASH_DURATION, &mut player, &grid);
+        assert_eq!(hen.hit_flash_time, 0.0);
+
+        // Shielded monsters take no damage, so they don't flash either.
+        hen.state = MonsterState::Shielded;
+        assert!(!hen.lose_health(1.0));
+        assert_eq!(hen.hit_flash_time, 0.0);
+        assert_eq!(hen.get_health(), 999.0);
+
+        hen.state = MonsterState::Walking;
+        assert!(hen.lose_health(999.0));
+    }
+
+    #[test]
+    fn stealth_hidden_until_detected() {
+        let registry = MonsterRegistry::from_ron_str(
+            "[(name: \"Ghost\", stealth: true, speed: 50.0, health: 10.0, size: 10.0, \
+               leak_damage: 1.0, loot: (gold: (1, 1)), walking_sprites: [\"/ghost.png\"], \
+               hurt_sound: \"/boo.ogg\")]",
+        )
+        .unwrap();
+        let mut ghost = GenericMonster::new(registry.get("Ghost").unwrap(), [0.0, 0.0], vec![]);
+        assert!(ghost.is_hidden());
+
+        ghost.set_detected(true);
+        assert!(ghost.is_stealthy());
+        assert!(!ghost.is_hidden());
+
+        // Monsters without stealth are never hidden.
+        assert!(!boss().is_hidden());
+    }
+
+    #[test]
+    fn attacks_towers_next_to_route() {
+        let registry = MonsterRegistry::from_ron_str(
+            "[(name: \"Goat\", speed: 35.0, health: 10.0, size: 10.0, leak_damage: 1.0, \
+               loot: (gold: (1, 1)), walking_sprites: [\"/goat.png\"], hurt_sound: \"/baa.ogg\", \
+               tower_attack: Some((damage: 5.0, range: 50.0, cooldown: 2.0)))]",
+        )
+        .unwrap();
+        let mut goat = GenericMonster::new(
+            registry.get("Goat").unwrap(),
+            [BLOCK_SIZE / 2.0, BLOCK_SIZE / 2.0],
+            vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0]],
+        );
+        let mut player = Player::new(100.0, 0);
+        let mut grid = Grid::new(5, 5);
+        grid.set([2, 1], Occupant::Tower(3));
+
+        // Walks one block, then stops next to the tower.
+        goat.update(1.0, &mut player, &grid);
+        goat.update(1.0, &mut player, &grid);
+        let position = goat.get_center_pos_abs();
+        assert_eq!(
+            goat.get_current_state(),
+            MonsterState::Attacking(AttackTarget::Tower(3))
+        );
+        assert_eq!(
+            goat.take_events(),
+            vec![MonsterEvent::AttackTower {
+                tower: 3,
+                damage: 5.0
+            }]
+        );
+
+        // Waits for the cooldown before attacking again.
+        goat.update(1.0, &mut player, &grid);
+        assert!(goat.take_events().is_empty());
+        goat.update(1.0, &mut player, &grid);
+        assert_eq!(goat.take_events().len(), 1);
+        assert_eq!(goat.get_center_pos_abs(), position);
+
+        // Continues once the tower is gone.
+        grid.set([2, 1], Occupant::Empty);
+        goat.update(1.0, &mut player, &grid);
+        assert_eq!(goat.get_current_state(), MonsterState::Walking);
+        assert_eq!(goat.distance_travelled(), 2.0 * BLOCK_SIZE);
+    }
+}
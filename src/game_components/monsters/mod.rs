@@ -1,8 +1,18 @@
-pub mod chicken;
-pub mod cool_chicken;
+pub mod aura;
+pub mod boss;
+pub mod generic_monster;
 pub mod monster;
+pub mod monster_definition;
+pub mod path_follower;
+pub mod status_effect;
 
-pub use self::chicken::Chicken;
-pub use self::cool_chicken::CoolChicken;
+pub use self::aura::{Aura, AuraEffects, AuraKind};
+pub use self::boss::{BossAbility, BossDefinition};
+pub use self::generic_monster::GenericMonster;
 pub use self::monster::Monster;
-pub use self::monster::MonsterState;
+pub use self::monster::{AttackTarget, MonsterEvent, MonsterId, MonsterState};
+pub use self::monster_definition::{
+    DeathEffect, MonsterDefinition, MonsterRegistry, MonsterType, MovementMode,
+};
+pub use self::path_follower::PathFollower;
+pub use self::status_effect::{StatusEffect, StatusEffects};
@@ -1,32 +1,139 @@
 use crate::{
     asset_system::AssetManager,
-    game_components::{Block, GoldPile, Player},
+    game_components::{
+        monsters::{Aura, AuraEffects, MonsterType, StatusEffect},
+        towers::TowerId,
+        Damage, Grid, Pickup, Player,
+    },
 };
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
-pub enum MonsterType {
-    Chicken,
-    CoolChicken,
+/// What an attacking monster is attacking.
+#[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd, Debug)]
+pub enum AttackTarget {
+    /// Reached the end of the route, hits the player and dies.
+    Base,
+    /// Stopped next to a tower to attack it.
+    Tower(TowerId),
 }
 
 #[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd, Debug)]
 pub enum MonsterState {
     Walking,
-    Attacking,
+    /// Walking, but immune to damage (e.g. a boss shield).
+    Shielded,
+    Attacking(AttackTarget),
     Dead,
 }
 
+/// Something a monster wants to happen outside of itself, e.g. summoning
+/// minions. Queued by the monster and handled by the main loop, since
+/// monsters can't access the board. Positions are absolute (pixels).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonsterEvent {
+    /// Spawns `count` monsters of the given type at `position`, following
+    /// `route`.
+    Summon {
+        monster: MonsterType,
+        count: u32,
+        position: [f32; 2],
+        route: Vec<[f32; 2]>,
+    },
+    /// Deals `damage` to a tower.
+    AttackTower { tower: TowerId, damage: f32 },
+    /// Stops all towers within `range` of `position` from attacking for
+    /// `duration` seconds.
+    DisableTowers {
+        position: [f32; 2],
+        range: f32,
+        duration: f32,
+    },
+}
+
+/// Identifies a monster on the board.
+pub type MonsterId = u32;
+
 pub trait Monster {
+    fn get_id(&self) -> MonsterId;
+
+    /// Called by the board when the monster is added to it.
+    fn set_id(&mut self, id: MonsterId);
+
     fn get_center_pos_abs(&self) -> [f32; 2];
 
+    /// Takes a hit, the damage actually taken depends on the damage type and
+    /// the armor and resistances of the monster.
     fn recieve_damage(
         &mut self,
-        damage: f32,
-        gold_piles: &mut Vec<GoldPile>,
+        damage: Damage,
+        pickups: &mut Vec<Pickup>,
+        asset_manager: &mut AssetManager,
+    );
+
+    /// Applies a status effect such as a slow or poison, see
+    /// `StatusEffects::apply` for how it combines with active effects.
+    fn apply_status_effect(&mut self, effect: StatusEffect);
+
+    /// Counts down the active status effects and deals their damage over
+    /// time, called once per frame.
+    fn update_status_effects(
+        &mut self,
+        elapsed: f32,
+        pickups: &mut Vec<Pickup>,
         asset_manager: &mut AssetManager,
     );
 
     fn get_current_state(&self) -> MonsterState;
 
-    fn update(&mut self, elapsed: f32, path_blocks: &Vec<Block>, player: &mut Player);
+    /// Moves the monster along its route, or attacks the target it stopped
+    /// at. `grid` is used to look up the terrain the monster is walking on
+    /// and towers next to it.
+    fn update(&mut self, elapsed: f32, player: &mut Player, grid: &Grid);
+
+    /// Replaces the route the monster is walking along, the monster starts
+    /// walking towards the first block of the new route.
+    fn set_route(&mut self, route: Vec<[f32; 2]>);
+
+    /// Pixels walked along the route(s) since spawning.
+    fn distance_travelled(&self) -> f32;
+
+    /// Pixels left to walk until the monster reaches the base.
+    fn distance_remaining(&self) -> f32;
+
+    /// Flying monsters ignore the path and can only be hit by anti-air
+    /// towers.
+    fn is_flying(&self) -> bool;
+
+    /// Whether the monster is stealthy, whether it is detected or not.
+    fn is_stealthy(&self) -> bool;
+
+    /// Called every frame before the towers attack, with whether the monster
+    /// is within range of a tower with detection.
+    fn set_detected(&mut self, detected: bool);
+
+    /// Stealthy monsters that aren't detected can't be targeted by towers.
+    fn is_hidden(&self) -> bool;
+
+    /// Current pixels per second including status effects, not counting
+    /// terrain.
+    fn get_speed(&self) -> f32;
+
+    /// Name of the monster type, e.g. "Chicken".
+    fn get_name(&self) -> &str;
+
+    fn get_health(&self) -> f32;
+
+    fn get_max_health(&self) -> f32;
+
+    /// Bosses are shown with a large health bar in the UI.
+    fn is_boss(&self) -> bool;
+
+    /// Buffs the monster gives to nearby monsters.
+    fn get_auras(&self) -> &[Aura];
+
+    /// Called every frame before `update`, with the combined effect of the
+    /// auras of nearby monsters.
+    fn set_aura_effects(&mut self, effects: AuraEffects);
+
+    /// Returns and clears the events queued since the last call.
+    fn take_events(&mut self) -> Vec<MonsterEvent>;
 }
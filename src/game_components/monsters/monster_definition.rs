@@ -0,0 +1,399 @@
+use crate::{
+    game_components::{
+        monsters::{Aura, BossDefinition},
+        Defenses, LootTable,
+    },
+    level_system::{read_resource, LevelError},
+};
+
+use ggez::Context;
+
+use serde::Deserialize;
+
+use std::rc::Rc;
+
+/// Name of a monster definition, as referenced by wave schedules.
+pub type MonsterType = String;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Deserialize)]
+pub enum MovementMode {
+    /// Walks along the routes of the board.
+    #[default]
+    Walking,
+    /// Flies in a straight line from the entrance to the base, ignoring the
+    /// path and terrain. Only anti-air towers can hit flying monsters.
+    Flying,
+}
+
+/// Something that happens when a monster is killed, but not when it reaches
+/// the base.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum DeathEffect {
+    /// Spawns `count` monsters where the monster died, continuing along the
+    /// rest of its route.
+    Spawn { monster: MonsterType, count: u32 },
+}
+
+/// Lets a monster stop next to towers and attack them.
+///
+/// Example:
+/// ```ron
+/// (damage: 10.0, range: 40.0, cooldown: 1.5)
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct TowerAttack {
+    pub damage: f32,
+    /// Pixels from the center of the monster to the center of the tower.
+    pub range: f32,
+    /// Seconds between attacks.
+    pub cooldown: f32,
+}
+
+fn default_animation_interval() -> u32 {
+    500
+}
+
+/// Properties shared by all monsters of one type, as described by an entry in
+/// `resources/monsters/monsters.ron`. Sizes and positions are in pixels,
+/// resource paths are relative to the resource directory.
+///
+/// Example:
+/// ```ron
+/// (
+///     name: "Chicken",
+///     movement: Walking,
+///     stealth: false,
+///     speed: 100.0,
+///     health: 100.0,
+///     defenses: (armor: 0.0, resistances: {Magic: 0.25}),
+///     size: 20.0,
+///     leak_damage: 1.0,
+///     loot: (gold: (8, 12), drops: [(chance: 0.1, pickup: Heal(5.0))]),
+///     walking_sprites: [
+///         "/monsters/chicken/chicken_run1.png",
+///         "/monsters/chicken/chicken_run2.png",
+///     ],
+///     animation_interval: 500,
+///     sprite_offset: (10.0, 0.0),
+///     hurt_sound: "/monsters/chicken_hurt.ogg",
+///     on_death: [Spawn(monster: "Chick", count: 2)],
+///     tower_attack: None,
+///     auras: [(kind: Haste, strength: 0.2, range: 60.0)],
+///     boss: None,
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MonsterDefinition {
+    pub name: MonsterType,
+    #[serde(default)]
+    pub movement: MovementMode,
+    /// Stealthy monsters can only be attacked by towers while detected by
+    /// a tower with detection.
+    #[serde(default)]
+    pub stealth: bool,
+    /// Pixels per second.
+    pub speed: f32,
+    pub health: f32,
+    /// Armor and resistances against the different damage types.
+    #[serde(default)]
+    pub defenses: Defenses,
+    pub size: f32,
+    /// Damage dealt to the player when the monster reaches the base.
+    pub leak_damage: f32,
+    /// Gold and other pickups dropped when the monster is killed.
+    pub loot: LootTable,
+    /// Frames of the looping walking animation.
+    pub walking_sprites: Vec<String>,
+    /// Milliseconds between animation frames.
+    #[serde(default = "default_animation_interval")]
+    pub animation_interval: u32,
+    /// Image specific offset of the sprites when facing right.
+    #[serde(default)]
+    pub sprite_offset: [f32; 2],
+    pub hurt_sound: String,
+    #[serde(default)]
+    pub on_death: Vec<DeathEffect>,
+    /// Set for monsters that attack towers on their way.
+    #[serde(default)]
+    pub tower_attack: Option<TowerAttack>,
+    /// Buffs given to nearby monsters.
+    #[serde(default)]
+    pub auras: Vec<Aura>,
+    /// Phases and abilities, only set for bosses.
+    #[serde(default)]
+    pub boss: Option<BossDefinition>,
+}
+
+impl MonsterDefinition {
+    /// Names of all monsters spawned when this monster is killed.
+    pub fn death_spawns(&self) -> impl Iterator<Item = &MonsterType> {
+        self.on_death.iter().map(|effect| match effect {
+            DeathEffect::Spawn { monster, .. } => monster,
+        })
+    }
+}
+
+/// A small, weak monster for tests, as a RON definition so tests can list it
+/// next to monsters that refer to it.
+#[cfg(test)]
+pub const TEST_CHICK: &str =
+    "(name: \"Chick\", speed: 50.0, health: 10.0, size: 10.0, leak_damage: 1.0, \
+      loot: (gold: (1, 1)), walking_sprites: [\"/chick.png\"], hurt_sound: \"/peep.ogg\")";
+
+#[cfg(test)]
+impl MonsterDefinition {
+    /// The definition of `TEST_CHICK`.
+    pub fn test_chick() -> Rc<MonsterDefinition> {
+        MonsterRegistry::from_ron_str(&format!("[{}]", TEST_CHICK))
+            .unwrap()
+            .get("Chick")
+            .unwrap()
+    }
+}
+
+/// All monster types the game knows about, loaded from a monster definition
+/// file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonsterRegistry {
+    definitions: Vec<Rc<MonsterDefinition>>,
+}
+
+impl MonsterRegistry {
+    /// Loads and validates a monster definition file from the ggez resource
+    /// directory, e.g. `/monsters/monsters.ron`.
+    pub fn load(ctx: &mut Context, path: &str) -> Result<MonsterRegistry, LevelError> {
+        MonsterRegistry::from_ron_str(&read_resource(ctx, path)?)
+    }
+
+    /// Parses and validates a list of monster definitions from its RON
+    /// representation.
+    pub fn from_ron_str(contents: &str) -> Result<MonsterRegistry, LevelError> {
+        let definitions: Vec<MonsterDefinition> =
+            ron::de::from_str(contents).map_err(|e| LevelError::Parse(e.to_string()))?;
+
+        for (index, definition) in definitions.iter().enumerate() {
+            let invalid = |reason| LevelError::InvalidMonster {
+                monster: definition.name.clone(),
+                reason,
+            };
+
+            if definitions[..index]
+                .iter()
+                .any(|other| other.name == definition.name)
+            {
+                return Err(invalid("defined more than once"));
+            }
+            if definition.walking_sprites.is_empty() {
+                return Err(invalid("has no walking sprites"));
+            }
+            if !definition.loot.is_valid() {
+                return Err(invalid(
+                    "loot gold range must be in order and drop chances between 0 and 1",
+                ));
+            }
+            if definition.health <= 0.0 {
+                return Err(invalid("health must be positive"));
+            }
+            if definition.speed < 0.0 || definition.size < 0.0 {
+                return Err(invalid("speed and size may not be negative"));
+            }
+            if let Some(attack) = definition.tower_attack {
+                if attack.damage < 0.0 || attack.range < 0.0 || attack.cooldown <= 0.0 {
+                    return Err(invalid(
+                        "tower attack damage and range may not be negative, cooldown must be positive",
+                    ));
+                }
+            }
+            if definition
+                .auras
+                .iter()
+                .any(|aura| aura.strength < 0.0 || aura.range < 0.0)
+            {
+                return Err(invalid("aura strength and range may not be negative"));
+            }
+            if let Some(boss) = &definition.boss {
+                if !boss.has_valid_phases() {
+                    return Err(invalid(
+                        "boss phase thresholds must be descending fractions between 0 and 1",
+                    ));
+                }
+                if boss
+                    .summoned_monsters()
+                    .any(|monster| definitions.iter().all(|other| other.name != *monster))
+                {
+                    return Err(invalid("boss summons an unknown monster"));
+                }
+            }
+            if definition
+                .death_spawns()
+                .any(|monster| definitions.iter().all(|other| other.name != *monster))
+            {
+                return Err(invalid("spawns an unknown monster on death"));
+            }
+            if has_death_spawn_cycle(&definitions, definition) {
+                return Err(invalid("spawns itself on death, directly or indirectly"));
+            }
+        }
+
+        Ok(MonsterRegistry {
+            definitions: definitions.into_iter().map(Rc::new).collect(),
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<MonsterDefinition>> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name == name)
+            .cloned()
+    }
+
+    pub fn definitions(&self) -> &[Rc<MonsterDefinition>] {
+        &self.definitions
+    }
+}
+
+/// Whether killing a monster can, through a chain of death spawns, spawn a
+/// monster of the same type again, which would never end.
+fn has_death_spawn_cycle(definitions: &[MonsterDefinition], start: &MonsterDefinition) -> bool {
+    let mut visited: Vec<&MonsterType> = Vec::new();
+    let mut pending: Vec<&MonsterType> = start.death_spawns().collect();
+
+    while let Some(name) = pending.pop() {
+        if *name == start.name {
+            return true;
+        }
+        if visited.contains(&name) {
+            continue;
+        }
+        visited.push(name);
+
+        if let Some(definition) = definitions.iter().find(|other| other.name == *name) {
+            pending.extend(definition.death_spawns());
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        monsters::{MonsterRegistry, MovementMode},
+        Defenses,
+    };
+    use crate::level_system::LevelError;
+
+    #[test]
+    fn default_monsters_are_valid() {
+        let registry =
+            MonsterRegistry::from_ron_str(include_str!("../../../resources/monsters/monsters.ron"))
+                .unwrap();
+
+        let chicken = registry.get("Chicken").unwrap();
+        assert_eq!(chicken.walking_sprites.len(), 2);
+        assert!(registry.get("CoolChicken").is_some());
+        assert_eq!(
+            registry.get("FlyingChicken").unwrap().movement,
+            MovementMode::Flying
+        );
+        assert!(registry.get("Dragon").is_none());
+
+        let big_chicken = registry.get("BigChicken").unwrap();
+        assert_eq!(
+            big_chicken.death_spawns().collect::<Vec<_>>(),
+            vec!["Chicken"]
+        );
+
+        let boss = registry.get("ChickenKing").unwrap();
+        assert!(boss.boss.as_ref().unwrap().phases.len() > 1);
+    }
+
+    #[test]
+    fn defaults_of_optional_fields() {
+        let registry = MonsterRegistry::from_ron_str(
+            "[(name: \"Duck\", speed: 50.0, health: 10.0, size: 20.0, leak_damage: 2.0, \
+               loot: (gold: (5, 5)), walking_sprites: [\"/duck.png\"], hurt_sound: \"/quack.ogg\")]",
+        )
+        .unwrap();
+
+        let duck = registry.get("Duck").unwrap();
+        assert_eq!(duck.animation_interval, 500);
+        assert_eq!(duck.sprite_offset, [0.0, 0.0]);
+        assert_eq!(duck.defenses, Defenses::default());
+        assert_eq!(duck.movement, MovementMode::Walking);
+        assert!(duck.boss.is_none());
+        assert!(duck.on_death.is_empty());
+        assert!(duck.auras.is_empty());
+        assert!(!duck.stealth);
+        assert!(duck.tower_attack.is_none());
+    }
+
+    #[test]
+    fn duplicate_monster() {
+        let duck = "(name: \"Duck\", speed: 50.0, health: 10.0, size: 20.0, leak_damage: 2.0, \
+                    loot: (gold: (5, 5)), walking_sprites: [\"/duck.png\"], hurt_sound: \"/quack.ogg\")";
+        let registry = MonsterRegistry::from_ron_str(&format!("[{}, {}]", duck, duck));
+
+        assert_eq!(
+            registry,
+            Err(LevelError::InvalidMonster {
+                monster: "Duck".to_string(),
+                reason: "defined more than once"
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_boss_phases() {
+        let boss = |phases: &str| {
+            MonsterRegistry::from_ron_str(&format!(
+                "[(name: \"Goose\", speed: 50.0, health: 1000.0, size: 40.0, leak_damage: 20.0, \
+                   loot: (gold: (100, 100)), walking_sprites: [\"/goose.png\"], hurt_sound: \"/honk.ogg\", \
+                   boss: Some((phases: [{}])))]",
+                phases
+            ))
+        };
+
+        assert!(boss("(health_threshold: 0.5, abilities: [Shield(duration: 2.0)])").is_ok());
+        assert_eq!(
+            boss("(health_threshold: 0.3, abilities: []), (health_threshold: 0.6, abilities: [])"),
+            Err(LevelError::InvalidMonster {
+                monster: "Goose".to_string(),
+                reason: "boss phase thresholds must be descending fractions between 0 and 1"
+            })
+        );
+        assert_eq!(
+            boss("(health_threshold: 0.5, abilities: [Summon(monster: \"Gosling\", count: 2)])"),
+            Err(LevelError::InvalidMonster {
+                monster: "Goose".to_string(),
+                reason: "boss summons an unknown monster"
+            })
+        );
+    }
+
+    #[test]
+    fn death_spawn_cycle() {
+        let monster = |name: &str, child: &str| {
+            format!(
+                "(name: \"{}\", speed: 50.0, health: 10.0, size: 20.0, leak_damage: 2.0, \
+                  loot: (gold: (5, 5)), walking_sprites: [\"/duck.png\"], hurt_sound: \"/quack.ogg\", \
+                  on_death: [Spawn(monster: \"{}\", count: 2)])",
+                name, child
+            )
+        };
+        let registry = MonsterRegistry::from_ron_str(&format!(
+            "[{}, {}, {}]",
+            monster("Egg", "Chick"),
+            monster("Chick", "Hen"),
+            monster("Hen", "Egg")
+        ));
+
+        assert_eq!(
+            registry,
+            Err(LevelError::InvalidMonster {
+                monster: "Egg".to_string(),
+                reason: "spawns itself on death, directly or indirectly"
+            })
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::game_components::BLOCK_SIZE;
+
+/// Moves a position along a route of blocks, from block center to block
+/// center, and keeps track of how far along the route it is. Positions are
+/// absolute (pixels), routes are block positions.
+pub struct PathFollower {
+    route: Vec<[f32; 2]>,
+    /// Pixels walked beside the block centers, see `waypoint`.
+    lateral_offset: f32,
+    /// Absolute positions walked towards, one for each route block.
+    waypoints: Vec<[f32; 2]>,
+    /// Index of the route block currently walked towards.
+    next_waypoint: usize,
+    position: [f32; 2],
+    /// Direction of the last movement, not normalized.
+    heading: [f32; 2],
+    distance_travelled: f32,
+    /// Distance from each route block to the end of the route.
+    remaining_from_waypoint: Vec<f32>,
+}
+
+impl PathFollower {
+    pub fn new(position: [f32; 2], route: Vec<[f32; 2]>) -> PathFollower {
+        let mut path_follower = PathFollower {
+            route: Vec::new(),
+            lateral_offset: 0.0,
+            waypoints: Vec::new(),
+            next_waypoint: 0,
+            position,
+            heading: [0.0, 0.0],
+            distance_travelled: 0.0,
+            remaining_from_waypoint: Vec::new(),
+        };
+        path_follower.set_route(route);
+        path_follower
+    }
+
+    /// Walks the whole route `lateral_offset` pixels beside the block
+    /// centers, also after the route is replaced.
+    pub fn with_lateral_offset(mut self, lateral_offset: f32) -> PathFollower {
+        self.lateral_offset = lateral_offset;
+        let route = std::mem::take(&mut self.route);
+        self.set_route(route);
+        self
+    }
+
+    /// Absolute position of route block `index`, its center moved
+    /// `lateral_offset` pixels sideways so that the follower walks alongside
+    /// the lane center, also around corners. Positive offsets are to the
+    /// right of the route.
+    pub fn waypoint(route: &[[f32; 2]], index: usize, lateral_offset: f32) -> [f32; 2] {
+        let center = block_center(route[index]);
+
+        let incoming = index
+            .checked_sub(1)
+            .and_then(|previous| right_of(route[previous], route[index]));
+        let outgoing = route
+            .get(index + 1)
+            .and_then(|&next| right_of(route[index], next));
+        let side = match (incoming, outgoing) {
+            (Some(incoming), Some(outgoing)) => {
+                let cos = incoming[0] * outgoing[0] + incoming[1] * outgoing[1];
+                if cos < -0.9 {
+                    // Turning back, there is no corner to walk around.
+                    outgoing
+                } else {
+                    // Far enough out to keep the offset from both sides.
+                    [
+                        (incoming[0] + outgoing[0]) / (1.0 + cos),
+                        (incoming[1] + outgoing[1]) / (1.0 + cos),
+                    ]
+                }
+            }
+            (Some(side), None) | (None, Some(side)) => side,
+            (None, None) => [0.0, 1.0],
+        };
+
+        [
+            center[0] + side[0] * lateral_offset,
+            center[1] + side[1] * lateral_offset,
+        ]
+    }
+
+    /// Replaces the route, the follower continues towards the first block of
+    /// the new route from where it is now.
+    pub fn set_route(&mut self, route: Vec<[f32; 2]>) {
+        let waypoints: Vec<[f32; 2]> = (0..route.len())
+            .map(|index| PathFollower::waypoint(&route, index, self.lateral_offset))
+            .collect();
+
+        let mut remaining_from_waypoint = vec![0.0; route.len()];
+        for index in (0..route.len().saturating_sub(1)).rev() {
+            remaining_from_waypoint[index] = remaining_from_waypoint[index + 1]
+                + distance(waypoints[index], waypoints[index + 1]);
+        }
+
+        self.route = route;
+        self.waypoints = waypoints;
+        self.next_waypoint = 0;
+        self.remaining_from_waypoint = remaining_from_waypoint;
+    }
+
+    pub fn get_position(&self) -> [f32; 2] {
+        self.position
+    }
+
+    pub fn get_heading(&self) -> [f32; 2] {
+        self.heading
+    }
+
+    /// The blocks of the route that haven't been reached yet.
+    pub fn remaining_route(&self) -> Vec<[f32; 2]> {
+        self.route[self.next_waypoint..].to_vec()
+    }
+
+    /// Whether the end of the route has been reached.
+    pub fn is_finished(&self) -> bool {
+        self.next_waypoint >= self.route.len()
+    }
+
+    /// Moves `distance` pixels along the route. Passing a block carries the
+    /// rest of the movement over to the next one, so large steps (e.g. after
+    /// a lag spike) don't cut corners or stop short. Stops at the end of the
+    /// route.
+    pub fn advance(&mut self, distance: f32) {
+        let mut left = distance;
+
+        while left > 0.0 && !self.is_finished() {
+            let goal = self.waypoints[self.next_waypoint];
+            let offset = [goal[0] - self.position[0], goal[1] - self.position[1]];
+            let to_goal = (offset[0] * offset[0] + offset[1] * offset[1]).sqrt();
+
+            if to_goal > 0.0 {
+                self.heading = offset;
+            }
+
+            if to_goal <= left {
+                // Reached the block, continue towards the next one.
+                self.position = goal;
+                self.next_waypoint += 1;
+                self.distance_travelled += to_goal;
+                left -= to_goal;
+            } else {
+                self.position[0] += offset[0] / to_goal * left;
+                self.position[1] += offset[1] / to_goal * left;
+                self.distance_travelled += left;
+                left = 0.0;
+            }
+        }
+    }
+
+    /// Pixels moved along the route(s) since the start.
+    pub fn distance_travelled(&self) -> f32 {
+        self.distance_travelled
+    }
+
+    /// Pixels left to walk until the end of the route.
+    pub fn distance_remaining(&self) -> f32 {
+        if self.is_finished() {
+            return 0.0;
+        }
+
+        let goal = self.waypoints[self.next_waypoint];
+        distance(self.position, goal) + self.remaining_from_waypoint[self.next_waypoint]
+    }
+}
+
+fn block_center(block: [f32; 2]) -> [f32; 2] {
+    [
+        block[0] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+        block[1] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+    ]
+}
+
+/// Unit vector pointing right of the direction from block `from` to block
+/// `to`, if they differ.
+fn right_of(from: [f32; 2], to: [f32; 2]) -> Option<[f32; 2]> {
+    let direction = [to[0] - from[0], to[1] - from[1]];
+    let length = (direction[0] * direction[0] + direction[1] * direction[1]).sqrt();
+    if length == 0.0 {
+        return None;
+    }
+    Some([-direction[1] / length, direction[0] / length])
+}
+
+fn distance(from: [f32; 2], to: [f32; 2]) -> f32 {
+    let dx = to[0] - from[0];
+    let dy = to[1] - from[1];
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{monsters::PathFollower, BLOCK_SIZE};
+
+    const HALF: f32 = BLOCK_SIZE / 2.0;
+
+    fn l_shaped_route() -> Vec<[f32; 2]> {
+        vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [2.0, 2.0]]
+    }
+
+    #[test]
+    fn distances_along_route() {
+        let mut path_follower = PathFollower::new([HALF, HALF], l_shaped_route());
+        assert_eq!(path_follower.distance_travelled(), 0.0);
+        assert_eq!(path_follower.distance_remaining(), 4.0 * BLOCK_SIZE);
+
+        path_follower.advance(BLOCK_SIZE / 2.0);
+        assert_eq!(path_follower.distance_travelled(), BLOCK_SIZE / 2.0);
+        assert_eq!(path_follower.distance_remaining(), 3.5 * BLOCK_SIZE);
+    }
+
+    #[test]
+    fn large_step_passes_several_blocks() {
+        let mut path_follower = PathFollower::new([HALF, HALF], l_shaped_route());
+
+        // Passes two blocks and turns the corner in a single step.
+        path_follower.advance(2.5 * BLOCK_SIZE);
+
+        assert_eq!(
+            path_follower.get_position(),
+            [2.0 * BLOCK_SIZE + HALF, BLOCK_SIZE / 2.0 + HALF]
+        );
+        assert_eq!(path_follower.distance_travelled(), 2.5 * BLOCK_SIZE);
+        assert_eq!(path_follower.distance_remaining(), 1.5 * BLOCK_SIZE);
+        assert!(path_follower.get_heading()[1] > 0.0);
+    }
+
+    #[test]
+    fn stops_at_end_of_route() {
+        let mut path_follower = PathFollower::new([HALF, HALF], l_shaped_route());
+
+        path_follower.advance(100.0 * BLOCK_SIZE);
+
+        assert!(path_follower.is_finished());
+        assert_eq!(
+            path_follower.get_position(),
+            [2.0 * BLOCK_SIZE + HALF, 2.0 * BLOCK_SIZE + HALF]
+        );
+        assert_eq!(path_follower.distance_travelled(), 4.0 * BLOCK_SIZE);
+        assert_eq!(path_follower.distance_remaining(), 0.0);
+    }
+
+    #[test]
+    fn keeps_lateral_offset() {
+        let route = l_shaped_route();
+        let start = PathFollower::waypoint(&route, 0, 5.0);
+        assert_eq!(start, [HALF, HALF + 5.0]);
+        let mut path_follower = PathFollower::new(start, route).with_lateral_offset(5.0);
+
+        // Keeps walking 5 pixels below the center of the lane, instead of
+        // heading back to it.
+        path_follower.advance(1.5 * BLOCK_SIZE);
+        assert_eq!(
+            path_follower.get_position(),
+            [1.5 * BLOCK_SIZE + HALF, HALF + 5.0]
+        );
+        assert_eq!(path_follower.get_heading()[1], 0.0);
+
+        // Turns the corner and keeps walking down, 5 pixels left of the
+        // center of the lane.
+        path_follower.advance(1.5 * BLOCK_SIZE);
+        assert_eq!(
+            path_follower.get_position(),
+            [2.0 * BLOCK_SIZE + HALF - 5.0, BLOCK_SIZE + HALF + 10.0]
+        );
+        assert_eq!(path_follower.get_heading()[0], 0.0);
+    }
+
+    #[test]
+    fn new_route_keeps_distance_travelled() {
+        let mut path_follower = PathFollower::new([HALF, HALF], l_shaped_route());
+        path_follower.advance(BLOCK_SIZE);
+
+        assert_eq!(
+            path_follower.remaining_route(),
+            l_shaped_route()[2..].to_vec()
+        );
+        path_follower.set_route(vec![[1.0, 0.0], [1.0, 1.0]]);
+
+        assert!(!path_follower.is_finished());
+        assert_eq!(path_follower.distance_travelled(), BLOCK_SIZE);
+        assert_eq!(path_follower.distance_remaining(), BLOCK_SIZE);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::game_components::{Damage, DamageType};
+
+use ggez::graphics::Color;
+
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum StatusEffectKind {
+    /// Reduces movement speed by `strength` (0.3 = 30% slower).
+    Slow,
+    /// Stops the monster from moving, `strength` is ignored.
+    Stun,
+    /// Deals `strength` true damage per second.
+    Poison,
+    /// Deals `strength` magic damage per second.
+    Burn,
+    /// Reduces the armor of the monster by `strength`.
+    ArmorBreak,
+    /// Increases all damage taken by `strength` (0.5 = 50% more damage).
+    Vulnerability,
+}
+
+/// What happens when an effect is applied to a monster already affected by
+/// an effect of the same kind.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum StackingPolicy {
+    /// The new effect replaces the old one, resetting the duration.
+    Refresh,
+    /// Both effects are active, their strengths add up.
+    Stack,
+    /// Only the stronger effect is kept, for equal strengths the longer
+    /// lasting one.
+    StrongestWins,
+}
+
+/// A temporary effect on a monster, e.g. applied by a tower attack.
+///
+/// Example:
+/// ```ron
+/// (kind: Slow, strength: 0.3, duration: 2.0, stacking: StrongestWins)
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub strength: f32,
+    /// Seconds.
+    pub duration: f32,
+    pub stacking: StackingPolicy,
+}
+
+/// The status effects currently active on a monster.
+#[derive(Debug, Clone, Default)]
+pub struct StatusEffects {
+    active: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Slows never stop a monster completely, that's what stuns are for.
+    pub const MAX_SLOW: f32 = 0.9;
+
+    pub fn apply(&mut self, effect: StatusEffect) {
+        let existing = self
+            .active
+            .iter()
+            .position(|active| active.kind == effect.kind);
+
+        match (effect.stacking, existing) {
+            (StackingPolicy::Stack, _) | (_, None) => self.active.push(effect),
+            (StackingPolicy::Refresh, Some(index)) => self.active[index] = effect,
+            (StackingPolicy::StrongestWins, Some(index)) => {
+                let active = &mut self.active[index];
+                let stronger = effect.strength > active.strength;
+                let longer =
+                    effect.strength == active.strength && effect.duration > active.duration;
+                if stronger || longer {
+                    *active = effect;
+                }
+            }
+        }
+    }
+
+    /// Counts down the durations and removes expired effects. Returns the
+    /// damage dealt by damage over time effects during `elapsed`.
+    pub fn update(&mut self, elapsed: f32) -> Vec<Damage> {
+        let mut damage = Vec::new();
+
+        for effect in self.active.iter_mut() {
+            let damage_type = match effect.kind {
+                StatusEffectKind::Poison => Some(DamageType::True),
+                StatusEffectKind::Burn => Some(DamageType::Magic),
+                _ => None,
+            };
+            if let Some(damage_type) = damage_type {
+                let amount = effect.strength * elapsed.min(effect.duration);
+                damage.push(Damage::new(amount, damage_type));
+            }
+            effect.duration -= elapsed;
+        }
+        self.active.retain(|effect| effect.duration > 0.0);
+
+        damage
+    }
+
+    pub fn is_active(&self, kind: StatusEffectKind) -> bool {
+        self.active.iter().any(|effect| effect.kind == kind)
+    }
+
+    /// Combined strength of all active effects of the given kind.
+    pub fn strength(&self, kind: StatusEffectKind) -> f32 {
+        self.active
+            .iter()
+            .filter(|effect| effect.kind == kind)
+            .map(|effect| effect.strength)
+            .sum()
+    }
+
+    /// Multiplier of the movement speed of the monster.
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_active(StatusEffectKind::Stun) {
+            return 0.0;
+        }
+        1.0 - self
+            .strength(StatusEffectKind::Slow)
+            .clamp(0.0, StatusEffects::MAX_SLOW)
+    }
+
+    /// Multiplier of all damage taken by the monster.
+    pub fn damage_multiplier(&self) -> f32 {
+        1.0 + self.strength(StatusEffectKind::Vulnerability).max(0.0)
+    }
+
+    pub fn armor_reduction(&self) -> f32 {
+        self.strength(StatusEffectKind::ArmorBreak)
+    }
+
+    /// Color to tint the monster with, showing the most important active
+    /// effect.
+    pub fn tint(&self) -> Option<Color> {
+        let kind = [
+            StatusEffectKind::Stun,
+            StatusEffectKind::Burn,
+            StatusEffectKind::Poison,
+            StatusEffectKind::Slow,
+            StatusEffectKind::Vulnerability,
+            StatusEffectKind::ArmorBreak,
+        ]
+        .iter()
+        .copied()
+        .find(|&kind| self.is_active(kind))?;
+
+        Some(match kind {
+            StatusEffectKind::Stun => Color::new(1.0, 1.0, 0.3, 1.0),
+            StatusEffectKind::Burn => Color::new(1.0, 0.5, 0.2, 1.0),
+            StatusEffectKind::Poison => Color::new(0.4, 1.0, 0.4, 1.0),
+            StatusEffectKind::Slow => Color::new(0.5, 0.7, 1.0, 1.0),
+            StatusEffectKind::Vulnerability => Color::new(0.9, 0.4, 1.0, 1.0),
+            StatusEffectKind::ArmorBreak => Color::new(0.7, 0.7, 0.7, 1.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        monsters::status_effect::{StackingPolicy, StatusEffect, StatusEffectKind, StatusEffects},
+        Damage, DamageType,
+    };
+
+    fn effect(
+        kind: StatusEffectKind,
+        strength: f32,
+        duration: f32,
+        stacking: StackingPolicy,
+    ) -> StatusEffect {
+        StatusEffect {
+            kind,
+            strength,
+            duration,
+            stacking,
+        }
+    }
+
+    #[test]
+    fn refresh_replaces_effect() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(
+            StatusEffectKind::Slow,
+            0.5,
+            1.0,
+            StackingPolicy::Refresh,
+        ));
+        effects.update(0.8);
+        effects.apply(effect(
+            StatusEffectKind::Slow,
+            0.2,
+            1.0,
+            StackingPolicy::Refresh,
+        ));
+
+        assert_eq!(effects.strength(StatusEffectKind::Slow), 0.2);
+        effects.update(0.5);
+        assert!(effects.is_active(StatusEffectKind::Slow));
+    }
+
+    #[test]
+    fn stacking_effects_add_up() {
+        let mut effects = StatusEffects::default();
+        for _ in 0..3 {
+            effects.apply(effect(
+                StatusEffectKind::Poison,
+                2.0,
+                2.0,
+                StackingPolicy::Stack,
+            ));
+        }
+
+        let total = |damage: Vec<Damage>| damage.iter().map(|d| d.amount).sum::<f32>();
+        assert_eq!(total(effects.update(1.0)), 6.0);
+        // Only the remaining second is dealt.
+        let damage = effects.update(5.0);
+        assert_eq!(total(damage.clone()), 6.0);
+        assert!(damage.iter().all(|d| d.damage_type == DamageType::True));
+        assert!(!effects.is_active(StatusEffectKind::Poison));
+    }
+
+    #[test]
+    fn strongest_wins() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(
+            StatusEffectKind::Vulnerability,
+            0.5,
+            1.0,
+            StackingPolicy::StrongestWins,
+        ));
+        effects.apply(effect(
+            StatusEffectKind::Vulnerability,
+            0.25,
+            10.0,
+            StackingPolicy::StrongestWins,
+        ));
+        assert_eq!(effects.damage_multiplier(), 1.5);
+
+        effects.apply(effect(
+            StatusEffectKind::Vulnerability,
+            1.0,
+            1.0,
+            StackingPolicy::StrongestWins,
+        ));
+        assert_eq!(effects.damage_multiplier(), 2.0);
+    }
+
+    #[test]
+    fn slow_and_stun() {
+        let mut effects = StatusEffects::default();
+        assert_eq!(effects.speed_multiplier(), 1.0);
+
+        for _ in 0..4 {
+            effects.apply(effect(
+                StatusEffectKind::Slow,
+                0.5,
+                2.0,
+                StackingPolicy::Stack,
+            ));
+        }
+        assert_eq!(effects.speed_multiplier(), 1.0 - StatusEffects::MAX_SLOW);
+
+        effects.apply(effect(
+            StatusEffectKind::Stun,
+            0.0,
+            1.0,
+            StackingPolicy::Refresh,
+        ));
+        assert_eq!(effects.speed_multiplier(), 0.0);
+        assert!(effects.tint().is_some());
+
+        effects.update(1.5);
+        assert_eq!(effects.speed_multiplier(), 1.0 - StatusEffects::MAX_SLOW);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::game_components::BLOCK_SIZE;
+
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum ObstacleKind {
+    Tree,
+    Rock,
+}
+
+impl ObstacleKind {
+    /// Gold it costs to clear an obstacle of this kind, unless the map says
+    /// otherwise.
+    pub fn default_clear_cost(self) -> u32 {
+        match self {
+            ObstacleKind::Tree => 15,
+            ObstacleKind::Rock => 30,
+        }
+    }
+}
+
+/// Obstacle as listed by a map file, position in blocks.
+///
+/// Example:
+/// ```ron
+/// (kind: Rock, position: (3, 4), clear_cost: Some(50), gold_drop: 20)
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub struct ObstacleDefinition {
+    pub kind: ObstacleKind,
+    pub position: [i32; 2],
+    #[serde(default)]
+    pub clear_cost: Option<u32>,
+    /// Gold left behind when the obstacle is cleared, nothing is dropped if
+    /// 0.
+    #[serde(default)]
+    pub gold_drop: u32,
+}
+
+/// Tree, rock etc. blocking tower placement until the player pays to clear
+/// it.
+pub struct Obstacle {
+    pub position: [f32; 2],
+    pub kind: ObstacleKind,
+    pub clear_cost: u32,
+    pub gold_drop: u32,
+}
+
+impl Obstacle {
+    pub fn new(definition: &ObstacleDefinition) -> Obstacle {
+        Obstacle {
+            position: [definition.position[0] as f32, definition.position[1] as f32],
+            kind: definition.kind,
+            clear_cost: definition
+                .clear_cost
+                .unwrap_or_else(|| definition.kind.default_clear_cost()),
+            gold_drop: definition.gold_drop,
+        }
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let center = Point2 {
+            x: self.position[0] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+            y: self.position[1] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+        };
+
+        match self.kind {
+            ObstacleKind::Tree => {
+                let trunk = graphics::Mesh::new_rectangle(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    [-3.0, 0.0, 6.0, BLOCK_SIZE / 2.0 - 2.0].into(),
+                    graphics::Color::new(0.4, 0.25, 0.1, 1.0),
+                )?;
+                graphics::draw(ctx, &trunk, (center,))?;
+
+                let crown = graphics::Mesh::new_circle(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    Point2 { x: 0.0, y: -4.0 },
+                    BLOCK_SIZE / 3.0,
+                    0.5,
+                    graphics::Color::new(0.05, 0.35, 0.1, 1.0),
+                )?;
+                graphics::draw(ctx, &crown, (center,))?;
+            }
+            ObstacleKind::Rock => {
+                let rock = graphics::Mesh::new_polygon(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    &[
+                        Point2 { x: -14.0, y: 10.0 },
+                        Point2 { x: -10.0, y: -6.0 },
+                        Point2 { x: 2.0, y: -12.0 },
+                        Point2 { x: 13.0, y: -2.0 },
+                        Point2 { x: 14.0, y: 10.0 },
+                    ],
+                    graphics::Color::new(0.55, 0.55, 0.55, 1.0),
+                )?;
+                graphics::draw(ctx, &rock, (center,))?;
+            }
+        }
+
+        Ok(())
+    }
+}
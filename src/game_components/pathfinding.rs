@@ -0,0 +1,124 @@
+use crate::game_components::grid::Grid;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Finds the shortest path over the block grid from `start` to any of the
+/// `goals` using A*. Only horizontal/vertical steps are taken and only blocks
+/// for which `is_walkable` returns true are entered, the start block is
+/// always allowed. The returned path includes both the start and goal block.
+pub fn find_path<F>(
+    grid: &Grid,
+    start: [i32; 2],
+    goals: &[[i32; 2]],
+    is_walkable: F,
+) -> Option<Vec<[i32; 2]>>
+where
+    F: Fn([i32; 2]) -> bool,
+{
+    if goals.is_empty() {
+        return None;
+    }
+
+    // Manhattan distance to the closest goal, never overestimates.
+    let heuristic = |position: [i32; 2]| {
+        goals
+            .iter()
+            .map(|goal| (goal[0] - position[0]).abs() + (goal[1] - position[1]).abs())
+            .min()
+            .unwrap_or(0)
+    };
+
+    let mut came_from: HashMap<[i32; 2], [i32; 2]> = HashMap::new();
+    let mut cost: HashMap<[i32; 2], i32> = HashMap::new();
+    let mut open = BinaryHeap::new();
+
+    // The insertion counter makes the search order, and therefore the
+    // resulting path, deterministic between equally good candidates.
+    let mut counter = 0;
+    cost.insert(start, 0);
+    open.push(Reverse((heuristic(start), counter, start)));
+
+    while let Some(Reverse((_, _, current))) = open.pop() {
+        if goals.contains(&current) {
+            let mut path = vec![current];
+            let mut position = current;
+            while let Some(&previous) = came_from.get(&position) {
+                path.push(previous);
+                position = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let current_cost = cost[&current];
+        for next in grid.neighbors(current) {
+            if !is_walkable(next) {
+                continue;
+            }
+
+            let next_cost = current_cost + 1;
+            if cost.get(&next).is_none_or(|&c| next_cost < c) {
+                cost.insert(next, next_cost);
+                came_from.insert(next, current);
+                counter += 1;
+                open.push(Reverse((next_cost + heuristic(next), counter, next)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        grid::{Grid, Occupant},
+        pathfinding::find_path,
+    };
+
+    fn is_empty(grid: &Grid) -> impl Fn([i32; 2]) -> bool + '_ {
+        move |block| grid.is_free(block)
+    }
+
+    #[test]
+    fn straight_path() {
+        let grid = Grid::new(5, 5);
+        let path = find_path(&grid, [0, 0], &[[4, 0]], is_empty(&grid)).unwrap();
+        assert_eq!(path, vec![[0, 0], [1, 0], [2, 0], [3, 0], [4, 0]]);
+    }
+
+    #[test]
+    fn path_around_wall() {
+        // Wall along x = 2 with a gap at the bottom.
+        let mut grid = Grid::new(5, 5);
+        for y in 0..4 {
+            grid.set([2, y], Occupant::Obstacle);
+        }
+
+        let path = find_path(&grid, [0, 0], &[[4, 0]], is_empty(&grid)).unwrap();
+
+        assert_eq!(path.len(), 13);
+        assert!(path.contains(&[2, 4]));
+        for pair in path.windows(2) {
+            let distance = (pair[0][0] - pair[1][0]).abs() + (pair[0][1] - pair[1][1]).abs();
+            assert_eq!(distance, 1);
+        }
+    }
+
+    #[test]
+    fn no_path() {
+        let mut grid = Grid::new(5, 5);
+        for y in 0..5 {
+            grid.set([2, y], Occupant::Tower(y as u32));
+        }
+        assert_eq!(find_path(&grid, [0, 0], &[[4, 0]], is_empty(&grid)), None);
+    }
+
+    #[test]
+    fn closest_goal() {
+        let grid = Grid::new(5, 5);
+        let path = find_path(&grid, [2, 2], &[[0, 2], [4, 4]], is_empty(&grid)).unwrap();
+        assert_eq!(path, vec![[2, 2], [1, 2], [0, 2]]);
+    }
+}
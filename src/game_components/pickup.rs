@@ -0,0 +1,146 @@
+use crate::asset_system::AssetManager;
+
+use log::debug;
+
+use ggez::graphics::DrawParam;
+use ggez::mint::Point2;
+use ggez::{graphics, Context, GameResult};
+
+use rand::Rng;
+
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub enum PickupKind {
+    /// Gives the player gold.
+    Gold(u32),
+    /// Restores health of the player.
+    Heal(f32),
+    /// Multiplies the damage of all towers for `duration` seconds.
+    DamageBuff { multiplier: f32, duration: f32 },
+}
+
+/// Something lying on the board, e.g. dropped by a killed monster, that is
+/// picked up by moving the mouse over it.
+pub struct Pickup {
+    pub position: [f32; 2],
+    pub kind: PickupKind,
+}
+
+impl Pickup {
+    pub fn draw(&mut self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult {
+        let location = Point2 {
+            x: self.position[0],
+            y: self.position[1] - 10.0,
+        };
+
+        let sprite = match self.kind {
+            PickupKind::Gold(_) => &asset_manager.item_assets.gold_sprite,
+            PickupKind::Heal(_) => &asset_manager.item_assets.heal_sprite,
+            PickupKind::DamageBuff { .. } => &asset_manager.item_assets.damage_buff_sprite,
+        };
+
+        debug!("Pickup: draw: drawing at location ({:?})", location);
+        graphics::draw(ctx, sprite, DrawParam::default().dest(location))?;
+
+        Ok(())
+    }
+}
+
+/// A pickup dropped with the given chance (0.0 - 1.0).
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct LootDrop {
+    pub chance: f32,
+    pub pickup: PickupKind,
+}
+
+/// What a monster drops when it is killed.
+///
+/// Example:
+/// ```ron
+/// (gold: (8, 12), drops: [(chance: 0.1, pickup: Heal(5.0))])
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LootTable {
+    /// Gold dropped on every kill, anywhere between the two values
+    /// (inclusive).
+    pub gold: [u32; 2],
+    /// Bonus drops, each rolled separately.
+    #[serde(default)]
+    pub drops: Vec<LootDrop>,
+}
+
+impl LootTable {
+    /// Whether the gold range is in order and all chances are between 0 and
+    /// 1.
+    pub fn is_valid(&self) -> bool {
+        self.gold[0] <= self.gold[1]
+            && self
+                .drops
+                .iter()
+                .all(|drop| (0.0..=1.0).contains(&drop.chance))
+    }
+
+    /// Rolls the pickups dropped by a single kill.
+    pub fn roll(&self, rng: &mut impl Rng) -> Vec<PickupKind> {
+        let mut pickups = Vec::new();
+
+        let gold = rng.gen_range(self.gold[0]..=self.gold[1].max(self.gold[0]));
+        if gold > 0 {
+            pickups.push(PickupKind::Gold(gold));
+        }
+
+        for drop in self.drops.iter() {
+            if rng.gen::<f32>() < drop.chance {
+                pickups.push(drop.pickup);
+            }
+        }
+
+        pickups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::pickup::{LootTable, PickupKind};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn gold_within_range() {
+        let loot: LootTable = ron::de::from_str("(gold: (5, 8))").unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            match loot.roll(&mut rng).as_slice() {
+                [PickupKind::Gold(gold)] => assert!((5..=8).contains(gold)),
+                pickups => panic!("unexpected pickups {:?}", pickups),
+            }
+        }
+    }
+
+    #[test]
+    fn bonus_drops_by_chance() {
+        let loot: LootTable = ron::de::from_str(
+            "(gold: (0, 0), drops: [(chance: 1.0, pickup: Heal(5.0)), \
+                                    (chance: 0.0, pickup: Gold(100))])",
+        )
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(loot.is_valid());
+        for _ in 0..100 {
+            assert_eq!(loot.roll(&mut rng), vec![PickupKind::Heal(5.0)]);
+        }
+    }
+
+    #[test]
+    fn invalid_loot() {
+        let reversed: LootTable = ron::de::from_str("(gold: (8, 5))").unwrap();
+        assert!(!reversed.is_valid());
+
+        let chance: LootTable =
+            ron::de::from_str("(gold: (1, 1), drops: [(chance: 1.5, pickup: Heal(1.0))])").unwrap();
+        assert!(!chance.is_valid());
+    }
+}
@@ -1,4 +1,74 @@
+use crate::game_components::PickupKind;
+
 pub struct Player {
     pub health: f32,
     pub gold: u32,
+    /// Multiplier of the damage of all towers while a damage buff is active.
+    pub damage_buff: f32,
+    /// Seconds left of the damage buff.
+    pub damage_buff_time: f32,
+}
+
+impl Player {
+    pub fn new(health: f32, gold: u32) -> Player {
+        Player {
+            health,
+            gold,
+            damage_buff: 1.0,
+            damage_buff_time: 0.0,
+        }
+    }
+
+    /// Applies the effect of a pickup. A new damage buff replaces the active
+    /// one.
+    pub fn pick_up(&mut self, pickup: PickupKind) {
+        match pickup {
+            PickupKind::Gold(gold) => self.gold += gold,
+            PickupKind::Heal(health) => self.health += health,
+            PickupKind::DamageBuff {
+                multiplier,
+                duration,
+            } => {
+                self.damage_buff = multiplier;
+                self.damage_buff_time = duration;
+            }
+        }
+    }
+
+    /// Counts down the active damage buff.
+    pub fn update(&mut self, elapsed: f32) {
+        self.damage_buff_time = (self.damage_buff_time - elapsed).max(0.0);
+    }
+
+    /// Multiplier of the damage of all towers.
+    pub fn damage_multiplier(&self) -> f32 {
+        if self.damage_buff_time > 0.0 {
+            self.damage_buff
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{PickupKind, Player};
+
+    #[test]
+    fn pickup_effects() {
+        let mut player = Player::new(10.0, 0);
+        player.pick_up(PickupKind::Gold(15));
+        player.pick_up(PickupKind::Heal(5.0));
+        assert_eq!(player.gold, 15);
+        assert_eq!(player.health, 15.0);
+
+        player.pick_up(PickupKind::DamageBuff {
+            multiplier: 2.0,
+            duration: 3.0,
+        });
+        player.update(2.0);
+        assert_eq!(player.damage_multiplier(), 2.0);
+        player.update(2.0);
+        assert_eq!(player.damage_multiplier(), 1.0);
+    }
 }
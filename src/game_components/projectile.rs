@@ -0,0 +1,295 @@
+use crate::{
+    asset_system::AssetManager,
+    game_components::{
+        monsters::{Monster, MonsterId, StatusEffect},
+        Damage,
+    },
+};
+
+use ggez::graphics::{self, DrawParam};
+use ggez::mint::Point2;
+use ggez::{Context, GameResult};
+
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum Flight {
+    /// Follows the target until it hits it, or until the target is gone.
+    Homing,
+    /// Flies to where the target was when fired, which may be missed by a
+    /// moving target.
+    Ballistic,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub enum ProjectileSprite {
+    Cannonball,
+    Shuriken,
+}
+
+/// Damage dealt to all monsters around the impact of a projectile.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct Splash {
+    /// Pixels.
+    pub radius: f32,
+    /// How much less damage is dealt at the edge of the splash than at its
+    /// center, 0.0 meaning the same damage everywhere.
+    pub falloff: f32,
+}
+
+impl Splash {
+    /// Fraction of the damage dealt at `distance` pixels from the impact.
+    pub fn multiplier(&self, distance: f32) -> f32 {
+        if distance > self.radius {
+            return 0.0;
+        }
+        1.0 - self.falloff.clamp(0.0, 1.0) * distance / self.radius
+    }
+}
+
+/// How the projectiles of a tower fly and what they hit.
+///
+/// Example:
+/// ```ron
+/// (
+///     speed: 250.0,
+///     flight: Ballistic,
+///     hit_radius: 12.0,
+///     splash: Some((radius: 30.0, falloff: 0.5)),
+///     sprite: Cannonball,
+/// )
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub struct ProjectileStats {
+    /// Pixels per second.
+    pub speed: f32,
+    pub flight: Flight,
+    /// Pixels around the impact within which a monster is hit directly.
+    pub hit_radius: f32,
+    #[serde(default)]
+    pub splash: Option<Splash>,
+    pub sprite: ProjectileSprite,
+}
+
+/// A projectile fired by a tower, flying towards a monster. It deals its
+/// damage on impact, not when fired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projectile {
+    pub stats: ProjectileStats,
+    /// Absolute (pixel) position.
+    pub position: [f32; 2],
+    pub target: MonsterId,
+    /// Where the projectile is flying to, kept up to date with the position
+    /// of the target for homing projectiles.
+    pub destination: [f32; 2],
+    /// Damage dealt to the monster hit directly, splash damage is based on
+    /// it.
+    pub damage: Damage,
+    /// Status effects applied to the monster hit directly.
+    pub on_hit: Vec<StatusEffect>,
+    /// Whether the projectile can hit flying monsters.
+    pub hits_flying: bool,
+}
+
+impl Projectile {
+    /// Fires a projectile from `from_abs` at the given monster.
+    pub fn fire(
+        stats: ProjectileStats,
+        from_abs: [f32; 2],
+        target: &dyn Monster,
+        damage: Damage,
+        on_hit: &[StatusEffect],
+        hits_flying: bool,
+    ) -> Projectile {
+        Projectile {
+            stats,
+            position: from_abs,
+            target: target.get_id(),
+            destination: target.get_center_pos_abs(),
+            damage,
+            on_hit: on_hit.to_vec(),
+            hits_flying,
+        }
+    }
+
+    /// Moves the projectile towards its destination, following the target
+    /// at `target_position` (if it is still alive) when homing. Returns
+    /// whether the projectile reached its destination.
+    pub fn advance(&mut self, elapsed: f32, target_position: Option<[f32; 2]>) -> bool {
+        if let (Flight::Homing, Some(target_position)) = (self.stats.flight, target_position) {
+            self.destination = target_position;
+        }
+
+        let dx = self.destination[0] - self.position[0];
+        let dy = self.destination[1] - self.position[1];
+        let distance = (dx * dx + dy * dy).sqrt();
+        let step = self.stats.speed * elapsed;
+
+        if distance <= step {
+            self.position = self.destination;
+            return true;
+        }
+
+        self.position[0] += dx / distance * step;
+        self.position[1] += dy / distance * step;
+        false
+    }
+
+    /// Whether the projectile can hit the monster at all.
+    pub fn can_hit(&self, monster: &dyn Monster) -> bool {
+        !monster.is_flying() || self.hits_flying
+    }
+
+    /// Picks the monster hit directly on impact among `candidates` (index
+    /// and monster, all of them alive): the target if it's within the hit
+    /// radius, otherwise the monster closest to the impact.
+    pub fn find_hit(&self, candidates: &[(usize, &dyn Monster)]) -> Option<usize> {
+        let mut hit: Option<(usize, f32)> = None;
+        for &(index, monster) in candidates.iter() {
+            if !self.can_hit(monster) {
+                continue;
+            }
+
+            let distance = self.distance_to(monster);
+            if distance > self.stats.hit_radius {
+                continue;
+            }
+            if monster.get_id() == self.target {
+                return Some(index);
+            }
+            if hit.is_none_or(|(_, closest)| distance < closest) {
+                hit = Some((index, distance));
+            }
+        }
+        hit.map(|(index, _)| index)
+    }
+
+    /// Pixels between the projectile and the center of the monster.
+    pub fn distance_to(&self, monster: &dyn Monster) -> f32 {
+        let position = monster.get_center_pos_abs();
+        let dx = position[0] - self.position[0];
+        let dy = position[1] - self.position[1];
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    pub fn draw(&self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult {
+        let sprite = match self.stats.sprite {
+            ProjectileSprite::Cannonball => &asset_manager.tower_assets.cannonball_sprite,
+            ProjectileSprite::Shuriken => &asset_manager.tower_assets.shuriken_sprite,
+        };
+
+        let location = Point2 {
+            x: self.position[0],
+            y: self.position[1],
+        };
+        graphics::draw(
+            ctx,
+            sprite,
+            DrawParam::default()
+                .dest(location)
+                .offset(Point2 { x: 0.5, y: 0.5 }),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        monsters::{GenericMonster, Monster, MonsterDefinition},
+        projectile::{Flight, Projectile, ProjectileSprite, ProjectileStats, Splash},
+        Damage, DamageType, BLOCK_SIZE,
+    };
+
+    const STATS: ProjectileStats = ProjectileStats {
+        speed: 100.0,
+        flight: Flight::Homing,
+        hit_radius: 10.0,
+        splash: None,
+        sprite: ProjectileSprite::Shuriken,
+    };
+
+    /// Chickens with ids 0, 1 and 2, standing one block apart.
+    fn chickens() -> Vec<GenericMonster> {
+        (0..3)
+            .map(|block| {
+                let mut chicken = GenericMonster::new(
+                    MonsterDefinition::test_chick(),
+                    [(block as f32 + 0.5) * BLOCK_SIZE, BLOCK_SIZE / 2.0],
+                    vec![[block as f32, 0.0]],
+                );
+                chicken.set_id(block);
+                chicken
+            })
+            .collect()
+    }
+
+    #[test]
+    fn homing_follows_target() {
+        let chickens = chickens();
+        let damage = Damage::new(1.0, DamageType::Physical);
+        let mut projectile = Projectile::fire(STATS, [0.0, 0.0], &chickens[0], damage, &[], false);
+
+        assert!(!projectile.advance(0.1, Some([100.0, 0.0])));
+        assert_eq!(projectile.position, [10.0, 0.0]);
+        assert!(projectile.advance(1.0, Some([100.0, 0.0])));
+        assert_eq!(projectile.position, [100.0, 0.0]);
+
+        // Ballistic projectiles keep flying to where the target was.
+        let mut projectile = Projectile {
+            stats: ProjectileStats {
+                flight: Flight::Ballistic,
+                ..STATS
+            },
+            ..projectile
+        };
+        projectile.destination = [0.0, 0.0];
+        assert!(projectile.advance(1.0, Some([200.0, 0.0])));
+        assert_eq!(projectile.position, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn hits_target_before_closer_monsters() {
+        let chickens = chickens();
+        let candidates: Vec<(usize, &dyn Monster)> = chickens
+            .iter()
+            .enumerate()
+            .map(|(index, chicken)| (index, chicken as &dyn Monster))
+            .collect();
+        let damage = Damage::new(1.0, DamageType::Physical);
+        let mut projectile = Projectile::fire(
+            ProjectileStats {
+                hit_radius: BLOCK_SIZE,
+                ..STATS
+            },
+            [0.0, 0.0],
+            &chickens[2],
+            damage,
+            &[],
+            false,
+        );
+
+        projectile.position = [1.9 * BLOCK_SIZE, BLOCK_SIZE / 2.0];
+        assert_eq!(projectile.find_hit(&candidates), Some(2));
+
+        // Missed the target, hits whatever is closest instead.
+        projectile.position = [0.4 * BLOCK_SIZE, BLOCK_SIZE / 2.0];
+        assert_eq!(projectile.find_hit(&candidates), Some(0));
+
+        projectile.position = [0.0, 5.0 * BLOCK_SIZE];
+        assert_eq!(projectile.find_hit(&candidates), None);
+    }
+
+    #[test]
+    fn splash_falloff() {
+        let splash = Splash {
+            radius: 20.0,
+            falloff: 0.5,
+        };
+        assert_eq!(splash.multiplier(0.0), 1.0);
+        assert_eq!(splash.multiplier(10.0), 0.75);
+        assert_eq!(splash.multiplier(20.0), 0.5);
+        assert_eq!(splash.multiplier(21.0), 0.0);
+    }
+}
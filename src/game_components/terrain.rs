@@ -0,0 +1,82 @@
+use serde::Deserialize;
+
+/// Ground type of a block, which affects what can be built on it and how
+/// monsters move over it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Deserialize)]
+pub enum Terrain {
+    /// Open field, towers can be built here.
+    #[default]
+    Grass,
+    /// Neither buildable nor walkable.
+    Rock,
+    /// Not walkable, only towers able to build on water can be placed here.
+    Water,
+    /// Buildable, towers placed here get extra attack range.
+    HighGround,
+    /// Part of a route.
+    Path,
+    /// Part of a route, slows down monsters walking through it.
+    MudPath,
+}
+
+impl Terrain {
+    /// Attack range multiplier of a tower placed on this terrain.
+    pub const HIGH_GROUND_RANGE_MULTIPLIER: f32 = 1.25;
+    /// Speed multiplier of a monster walking through mud.
+    pub const MUD_SPEED_MULTIPLIER: f32 = 0.5;
+
+    /// Whether a tower can be placed on this terrain.
+    pub fn is_buildable(self, can_build_on_water: bool) -> bool {
+        match self {
+            Terrain::Grass | Terrain::HighGround => true,
+            Terrain::Water => can_build_on_water,
+            Terrain::Rock | Terrain::Path | Terrain::MudPath => false,
+        }
+    }
+
+    /// Whether monsters can walk over this terrain.
+    pub fn is_walkable(self) -> bool {
+        !matches!(self, Terrain::Rock | Terrain::Water)
+    }
+
+    pub fn range_multiplier(self) -> f32 {
+        match self {
+            Terrain::HighGround => Terrain::HIGH_GROUND_RANGE_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
+
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            Terrain::MudPath => Terrain::MUD_SPEED_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Rectangle of blocks with the same terrain, as listed by a map file. Both
+/// corners are included.
+///
+/// Example:
+/// ```ron
+/// (terrain: Water, from: (3, 4), to: (5, 6))
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+pub struct TerrainArea {
+    pub terrain: Terrain,
+    pub from: [i32; 2],
+    pub to: [i32; 2],
+}
+
+impl TerrainArea {
+    /// All blocks covered by the area.
+    pub fn blocks(&self) -> Vec<[i32; 2]> {
+        let mut blocks = Vec::new();
+        for y in self.from[1].min(self.to[1])..=self.from[1].max(self.to[1]) {
+            for x in self.from[0].min(self.to[0])..=self.from[0].max(self.to[0]) {
+                blocks.push([x, y]);
+            }
+        }
+        blocks
+    }
+}
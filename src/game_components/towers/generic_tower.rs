@@ -0,0 +1,370 @@
+use crate::{
+    asset_system::AssetManager,
+    game_components::{
+        block::BLOCK_SIZE,
+        monsters::{MonsterId, MonsterState, StatusEffect},
+        projectile::ProjectileStats,
+        towers::{
+            TargetingPolicy, Tower, TowerAbility, TowerDefinition, TowerId, TowerStats, Upgrade,
+            UpgradeProgress,
+        },
+        Damage, Projectile, Terrain,
+    },
+    game_views::{health_bar::draw_health_bar, monsters::MonsterView},
+};
+
+use rand::Rng;
+
+use ggez::{
+    audio::SoundSource,
+    graphics::{self, DrawParam},
+    mint::Point2,
+    Context, GameResult,
+};
+
+use std::rc::Rc;
+
+/// A tower of any type, its stats and abilities taken from its definition.
+pub struct GenericTower {
+    pub definition: Rc<TowerDefinition>,
+    pub id: TowerId,
+    pub position: [f32; 2],
+    /// Terrain the tower is placed on.
+    pub terrain: Terrain,
+    /// Seconds until the tower can attack again after being disabled.
+    pub disabled_time: f32,
+    pub health: f32,
+    pub damage_multiplier: f32,
+    pub attack_cooldown: f32,
+    /// Seconds until the next strike, for towers with the strike ability.
+    pub strike_cooldown: f32,
+    pub targeting: TargetingPolicy,
+    /// Ids of the monsters currently attacked.
+    pub targets: Vec<MonsterId>,
+    pub stats: TowerStats,
+    pub upgrades: UpgradeProgress,
+    /// Status effects applied by regular attacks.
+    on_hit: Vec<StatusEffect>,
+}
+
+impl GenericTower {
+    pub fn new(definition: Rc<TowerDefinition>, position: [f32; 2]) -> GenericTower {
+        let strike_cooldown = match GenericTower::strike(&definition) {
+            Some((_, cooldown, _)) => cooldown / 2.0,
+            None => 0.0,
+        };
+
+        GenericTower {
+            id: 0,
+            position,
+            terrain: Terrain::default(),
+            disabled_time: 0.0,
+            health: definition.health,
+            damage_multiplier: 1.0,
+            attack_cooldown: definition.initial_cooldown,
+            strike_cooldown,
+            targeting: TargetingPolicy::default(),
+            targets: Vec::new(),
+            stats: definition.stats.clone(),
+            upgrades: UpgradeProgress::new(definition.upgrades.clone()),
+            on_hit: definition.on_hit(),
+            definition,
+        }
+    }
+
+    /// Indices of the monsters the strike ability may hit, anywhere on the
+    /// board.
+    fn strike_targets(&self, monster_views: &[Box<dyn MonsterView>]) -> Vec<usize> {
+        (0..monster_views.len())
+            .filter(|&index| {
+                let monster = monster_views[index].get_monster();
+                monster.get_current_state() != MonsterState::Dead && self.can_target(monster)
+            })
+            .collect()
+    }
+
+    /// Damage, cooldown and projectile speed of the strike ability, if the
+    /// tower has it.
+    fn strike(definition: &TowerDefinition) -> Option<(Damage, f32, f32)> {
+        definition
+            .abilities
+            .iter()
+            .find_map(|ability| match *ability {
+                TowerAbility::Strike {
+                    damage,
+                    cooldown,
+                    speed,
+                } => Some((damage, cooldown, speed)),
+                _ => None,
+            })
+    }
+}
+
+impl Tower for GenericTower {
+    fn draw(&mut self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult {
+        let location = Point2 {
+            x: self.position[0] * BLOCK_SIZE - 5.0,
+            y: self.position[1] * BLOCK_SIZE - 35.0,
+        };
+
+        if let Some(sprite) = asset_manager.tower_assets.get_sprite(&self.stats.sprite) {
+            graphics::draw(
+                ctx,
+                sprite,
+                DrawParam::default().dest(location).color(self.tint()),
+            )?;
+        }
+
+        draw_health_bar(
+            ctx,
+            [self.get_center_pos_abs()[0], location.y],
+            self.health,
+            self.definition.health,
+        )?;
+
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        elapsed: f32,
+        monster_views: &[Box<dyn MonsterView>], //TODO: workaround to make separating monster component/view easier.
+        projectiles: &mut Vec<Projectile>,
+        asset_manager: &mut AssetManager,
+    ) {
+        debug!(
+            "update: elapsed ({}), monsters length ({}), projectiles length ({}).",
+            elapsed,
+            monster_views.len(),
+            projectiles.len()
+        );
+        self.disabled_time = (self.disabled_time - elapsed).max(0.0);
+        if self.disabled_time > 0.0 {
+            self.targets.clear();
+            return;
+        }
+
+        self.targets = self.pick_targets(
+            monster_views,
+            self.targets.first().copied(),
+            self.stats.targets,
+        );
+
+        self.attack_cooldown = (self.attack_cooldown - elapsed).max(0.0);
+        self.strike_cooldown = (self.strike_cooldown - elapsed).max(0.0);
+
+        if self.attack_cooldown == 0.0 && !self.targets.is_empty() {
+            for monster in monster_views
+                .iter()
+                .map(|monster_view| monster_view.get_monster())
+                .filter(|monster| self.targets.contains(&monster.get_id()))
+            {
+                projectiles.push(Projectile::fire(
+                    self.stats.projectile,
+                    self.get_center_pos_abs(),
+                    monster,
+                    self.get_damage(),
+                    &self.on_hit,
+                    self.is_anti_air(),
+                ));
+            }
+            info!(
+                "update: fired at {} monster(s)! Playing attack soundeffect.",
+                self.targets.len()
+            );
+            asset_manager
+                .tower_assets
+                .tower_attack_sound
+                .play()
+                .unwrap();
+            self.attack_cooldown = self.stats.attack_timer;
+        }
+
+        let (damage, cooldown, speed) = match GenericTower::strike(&self.definition) {
+            Some(strike) => strike,
+            None => return,
+        };
+        let targets = self.strike_targets(monster_views);
+        if self.strike_cooldown == 0.0 && !targets.is_empty() {
+            let target = targets[rand::thread_rng().gen_range(0..targets.len())];
+            // The strike can hit anywhere on the board, so it flies faster.
+            projectiles.push(Projectile::fire(
+                ProjectileStats {
+                    speed,
+                    ..self.stats.projectile
+                },
+                self.get_center_pos_abs(),
+                monster_views[target].get_monster(),
+                damage.scaled(self.damage_multiplier),
+                &[],
+                self.is_anti_air(),
+            ));
+            asset_manager.tower_assets.strike_sound.play().unwrap();
+            self.strike_cooldown = cooldown;
+        }
+    }
+
+    fn get_block_position(&self) -> [f32; 2] {
+        self.position
+    }
+
+    fn get_center_pos_abs(&self) -> [f32; 2] {
+        [
+            self.position[0] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+            self.position[1] * BLOCK_SIZE + BLOCK_SIZE / 2.0,
+        ]
+    }
+
+    fn attack_range(&self) -> f32 {
+        self.stats.attack_range * self.terrain.range_multiplier()
+    }
+
+    fn get_targeting(&self) -> TargetingPolicy {
+        self.targeting
+    }
+
+    fn set_targeting(&mut self, targeting: TargetingPolicy) {
+        self.targeting = targeting;
+    }
+
+    fn get_damage(&self) -> Damage {
+        self.stats.damage.scaled(self.damage_multiplier)
+    }
+
+    fn get_invested_gold(&self) -> u32 {
+        self.definition.cost + self.upgrades.spent
+    }
+
+    fn get_level(&self) -> u32 {
+        self.upgrades.level
+    }
+
+    fn get_upgrades(&self) -> &[Upgrade] {
+        &self.upgrades.available
+    }
+
+    fn upgrade(&mut self, choice: usize) -> bool {
+        match self.upgrades.take(choice) {
+            Some(upgrade) => {
+                self.stats.apply(&upgrade);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn set_damage_multiplier(&mut self, multiplier: f32) {
+        self.damage_multiplier = multiplier;
+    }
+
+    fn get_id(&self) -> TowerId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: TowerId) {
+        self.id = id;
+    }
+
+    fn set_terrain(&mut self, terrain: Terrain) {
+        self.terrain = terrain;
+    }
+
+    fn disable(&mut self, duration: f32) {
+        self.disabled_time = self.disabled_time.max(duration);
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.disabled_time > 0.0
+    }
+
+    fn get_health(&self) -> f32 {
+        self.health
+    }
+
+    fn get_max_health(&self) -> f32 {
+        self.definition.health
+    }
+
+    fn take_damage(&mut self, damage: f32) {
+        self.health -= damage;
+    }
+
+    fn detection_range(&self) -> Option<f32> {
+        if self.definition.has_detection() {
+            Some(self.attack_range())
+        } else {
+            None
+        }
+    }
+
+    fn is_anti_air(&self) -> bool {
+        self.definition.is_anti_air()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animation_system::Animation;
+    use crate::game_components::{
+        monsters::{GenericMonster, MonsterDefinition, MonsterState},
+        towers::{GenericTower, Tower, TowerRegistry},
+    };
+    use crate::game_views::monsters::{GenericMonsterView, MonsterView};
+
+    #[test]
+    fn ninja_specializes() {
+        let registry =
+            TowerRegistry::from_ron_str(include_str!("../../../resources/towers/towers.ron"))
+                .unwrap();
+        let ninja = registry.get("Ninja").unwrap();
+        let mut tower = GenericTower::new(ninja.clone(), [0.0, 0.0]);
+        assert_eq!(tower.attack_cooldown, 2.0);
+        assert_eq!(tower.strike_cooldown, 5.0);
+        assert_eq!(tower.on_hit.len(), 1);
+        assert_eq!(tower.get_upgrades().len(), 1);
+        assert!(!tower.upgrade(1));
+
+        assert!(tower.upgrade(0));
+        let names: Vec<&str> = tower
+            .get_upgrades()
+            .iter()
+            .map(|upgrade| upgrade.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Assassin", "Shuriken Master"]);
+
+        assert!(tower.upgrade(1));
+        assert_eq!(tower.get_level(), 2);
+        assert_eq!(tower.stats.targets, 3);
+        assert_eq!(tower.stats.sprite, "/tower_shuriken_master.png");
+        assert!(tower.stats.attack_timer < ninja.stats.attack_timer);
+        assert_eq!(tower.get_upgrades()[0].name, "Shuriken Storm");
+        assert_eq!(tower.get_invested_gold(), 20 + 20 + 50);
+    }
+
+    #[test]
+    fn strike_skips_dead_monsters() {
+        let registry =
+            TowerRegistry::from_ron_str(include_str!("../../../resources/towers/towers.ron"))
+                .unwrap();
+        let tower = GenericTower::new(registry.get("Ninja").unwrap(), [0.0, 0.0]);
+        let monster_views: Vec<Box<dyn MonsterView>> = [MonsterState::Dead, MonsterState::Walking]
+            .iter()
+            .map(|&state| {
+                let mut monster =
+                    GenericMonster::new(MonsterDefinition::test_chick(), [0.0, 0.0], vec![]);
+                monster.state = state;
+                Box::new(GenericMonsterView {
+                    monster,
+                    animations: Animation {
+                        current_sprite: 0,
+                        next_sprite_interval: 0,
+                        next_sprite_time: 0,
+                        sprites: Vec::new(),
+                    },
+                }) as Box<dyn MonsterView>
+            })
+            .collect();
+
+        assert_eq!(tower.strike_targets(&monster_views), vec![1]);
+    }
+}
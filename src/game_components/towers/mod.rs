@@ -1,8 +1,12 @@
-pub mod basic_tower;
-pub mod ninja_tower;
+pub mod generic_tower;
+pub mod targeting;
 pub mod tower;
+pub mod tower_definition;
+pub mod upgrade;
 
-pub use self::basic_tower::BasicTower;
-pub use self::ninja_tower::NinjaTower;
+pub use self::generic_tower::GenericTower;
+pub use self::targeting::TargetingPolicy;
 pub use self::tower::Tower;
-pub use self::tower::TowerType;
+pub use self::tower::TowerId;
+pub use self::tower_definition::{TowerAbility, TowerDefinition, TowerRegistry, TowerType};
+pub use self::upgrade::{TowerStats, Upgrade, UpgradeProgress};
@@ -0,0 +1,170 @@
+use crate::game_components::monsters::{Monster, MonsterId};
+
+use rand::seq::SliceRandom;
+
+/// Which of the monsters in range a tower attacks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TargetingPolicy {
+    /// The monster closest to the base (along its route).
+    #[default]
+    First,
+    /// The monster furthest from the base (along its route).
+    Last,
+    /// The monster with the most health left.
+    Strongest,
+    /// The monster with the least health left.
+    Weakest,
+    /// The monster closest to the tower.
+    Closest,
+    /// Any monster, kept until it leaves the range.
+    Random,
+}
+
+impl TargetingPolicy {
+    /// The policy after this one, for cycling through all policies.
+    pub fn next(self) -> TargetingPolicy {
+        match self {
+            TargetingPolicy::First => TargetingPolicy::Last,
+            TargetingPolicy::Last => TargetingPolicy::Strongest,
+            TargetingPolicy::Strongest => TargetingPolicy::Weakest,
+            TargetingPolicy::Weakest => TargetingPolicy::Closest,
+            TargetingPolicy::Closest => TargetingPolicy::Random,
+            TargetingPolicy::Random => TargetingPolicy::First,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TargetingPolicy::First => "FIRST",
+            TargetingPolicy::Last => "LAST",
+            TargetingPolicy::Strongest => "STRONGEST",
+            TargetingPolicy::Weakest => "WEAKEST",
+            TargetingPolicy::Closest => "CLOSEST",
+            TargetingPolicy::Random => "RANDOM",
+        }
+    }
+
+    /// Picks the id of the monster to attack among `candidates` (all of them
+    /// attackable), for a tower centered at `tower_center_abs`. `current` is
+    /// the previous target, which random targeting sticks to while it is
+    /// still a candidate. Ties go to the first candidate.
+    pub fn pick(
+        self,
+        tower_center_abs: [f32; 2],
+        candidates: &[&dyn Monster],
+        current: Option<MonsterId>,
+    ) -> Option<MonsterId> {
+        let key: fn([f32; 2], &dyn Monster) -> f32 = match self {
+            TargetingPolicy::First => |_, monster| monster.distance_remaining(),
+            TargetingPolicy::Last => |_, monster| -monster.distance_remaining(),
+            TargetingPolicy::Strongest => |_, monster| -monster.get_health(),
+            TargetingPolicy::Weakest => |_, monster| monster.get_health(),
+            TargetingPolicy::Closest => |tower_center_abs, monster| {
+                let position = monster.get_center_pos_abs();
+                let dx = position[0] - tower_center_abs[0];
+                let dy = position[1] - tower_center_abs[1];
+                dx * dx + dy * dy
+            },
+            TargetingPolicy::Random => {
+                if let Some(current) = current {
+                    if candidates.iter().any(|monster| monster.get_id() == current) {
+                        return Some(current);
+                    }
+                }
+                return candidates
+                    .choose(&mut rand::thread_rng())
+                    .map(|monster| monster.get_id());
+            }
+        };
+
+        let mut best: Option<(MonsterId, f32)> = None;
+        for &monster in candidates.iter() {
+            let value = key(tower_center_abs, monster);
+            if best.is_none_or(|(_, best_value)| value < best_value) {
+                best = Some((monster.get_id(), value));
+            }
+        }
+        best.map(|(id, _)| id)
+    }
+}
+
+/// Whether the absolute (pixel) position is within `range` pixels of the
+/// tower center.
+pub fn is_in_range(tower_center_abs: [f32; 2], position_abs: [f32; 2], range: f32) -> bool {
+    let dx = tower_center_abs[0] - position_abs[0];
+    let dy = tower_center_abs[1] - position_abs[1];
+
+    dx * dx + dy * dy < range * range
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        monsters::{GenericMonster, Monster, MonsterDefinition},
+        towers::targeting::{is_in_range, TargetingPolicy},
+        BLOCK_SIZE,
+    };
+
+    /// Three chickens along the same route, the further along the route the
+    /// less health they have left.
+    fn chickens() -> Vec<GenericMonster> {
+        (0..3)
+            .map(|block| {
+                let mut chicken = GenericMonster::new(
+                    MonsterDefinition::test_chick(),
+                    [(block as f32 + 0.5) * BLOCK_SIZE, BLOCK_SIZE / 2.0],
+                    vec![[block as f32, 0.0], [3.0, 0.0]],
+                );
+                chicken.id = block;
+                chicken.health = 10.0 - block as f32 * 3.0;
+                chicken
+            })
+            .collect()
+    }
+
+    #[test]
+    fn picks_by_policy() {
+        let chickens = chickens();
+        let candidates: Vec<&dyn Monster> = chickens
+            .iter()
+            .map(|chicken| chicken as &dyn Monster)
+            .collect();
+        let tower_center = [0.0, BLOCK_SIZE / 2.0];
+
+        let pick = |policy: TargetingPolicy| policy.pick(tower_center, &candidates, None);
+        assert_eq!(pick(TargetingPolicy::First), Some(2));
+        assert_eq!(pick(TargetingPolicy::Last), Some(0));
+        assert_eq!(pick(TargetingPolicy::Strongest), Some(0));
+        assert_eq!(pick(TargetingPolicy::Weakest), Some(2));
+        assert_eq!(pick(TargetingPolicy::Closest), Some(0));
+        assert!(pick(TargetingPolicy::Random).is_some());
+
+        // Random sticks to its target while it can.
+        for _ in 0..10 {
+            assert_eq!(
+                TargetingPolicy::Random.pick(tower_center, &candidates, Some(1)),
+                Some(1)
+            );
+        }
+        assert_eq!(
+            TargetingPolicy::Random.pick(tower_center, &candidates[..1], Some(1)),
+            Some(0)
+        );
+        assert_eq!(TargetingPolicy::First.pick(tower_center, &[], None), None);
+    }
+
+    #[test]
+    fn cycles_through_all_policies() {
+        let mut policy = TargetingPolicy::default();
+        for _ in 0..6 {
+            policy = policy.next();
+        }
+        assert_eq!(policy, TargetingPolicy::First);
+    }
+
+    #[test]
+    fn range_is_exclusive() {
+        assert!(is_in_range([0.0, 0.0], [3.0, 4.0], 5.1));
+        assert!(!is_in_range([0.0, 0.0], [3.0, 4.0], 5.0));
+    }
+}
@@ -1,31 +1,161 @@
 use crate::{
-    asset_system::AssetManager, game_components::GoldPile, game_views::monsters::MonsterView,
+    asset_system::AssetManager,
+    game_components::{
+        monsters::{Monster, MonsterId, MonsterState},
+        towers::{
+            targeting::{is_in_range, TargetingPolicy},
+            Upgrade,
+        },
+        Damage, Projectile, Terrain,
+    },
+    game_views::monsters::MonsterView,
 };
 
-use ggez::{Context, GameResult};
+use ggez::{graphics::Color, Context, GameResult};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum TowerType {
-    Basic,
-    Ninja,
-}
+/// Tint of towers that are disabled, e.g. by a boss.
+pub const DISABLED_COLOR: Color = Color {
+    r: 0.4,
+    g: 0.4,
+    b: 0.6,
+    a: 1.0,
+};
+
+/// Identifies a tower placed on the board.
+pub type TowerId = u32;
 
 pub trait Tower {
     fn draw(&mut self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult;
 
-    fn draw_abilities(
-        &mut self,
-        ctx: &mut Context,
-        monster_views: &Vec<Box<dyn MonsterView>>,
-    ) -> GameResult;
-
+    /// Picks a target and fires projectiles at it, which hit the monsters
+    /// once they arrive.
     fn update(
         &mut self,
         elapsed: f32,
-        monster_views: &mut Vec<Box<dyn MonsterView>>, //TODO: workaround to make separating monster component/view easier.
-        gold_piles: &mut Vec<GoldPile>,
+        monster_views: &[Box<dyn MonsterView>], //TODO: workaround to make separating monster component/view easier.
+        projectiles: &mut Vec<Projectile>,
         asset_manager: &mut AssetManager,
     );
 
     fn get_block_position(&self) -> [f32; 2];
+
+    fn get_center_pos_abs(&self) -> [f32; 2];
+
+    /// Attack range in pixels, including any bonus of the terrain the tower
+    /// is placed on.
+    fn attack_range(&self) -> f32;
+
+    fn get_targeting(&self) -> TargetingPolicy;
+
+    /// Changes which monsters in range the tower attacks, e.g. by the
+    /// player.
+    fn set_targeting(&mut self, targeting: TargetingPolicy);
+
+    /// Damage (and its type) of a regular attack of the tower, including
+    /// any damage multiplier.
+    fn get_damage(&self) -> Damage;
+
+    /// Called every frame before `update`, with the multiplier of all
+    /// tower damage (e.g. from a damage buff pickup).
+    fn set_damage_multiplier(&mut self, multiplier: f32);
+
+    fn get_id(&self) -> TowerId;
+
+    /// Called by the board when the tower is placed.
+    fn set_id(&mut self, id: TowerId);
+
+    /// Gold spent on the tower, including all upgrades.
+    fn get_invested_gold(&self) -> u32;
+
+    fn get_level(&self) -> u32;
+
+    /// Upgrades that can be bought for the tower next, more than one being
+    /// a choice between specializations.
+    fn get_upgrades(&self) -> &[Upgrade];
+
+    /// Applies the available upgrade at index `choice`. Returns whether
+    /// there was such an upgrade, paying for it is up to the caller.
+    fn upgrade(&mut self, choice: usize) -> bool;
+
+    /// Called by the board when the tower is placed, with the terrain of the
+    /// block the tower is placed on.
+    fn set_terrain(&mut self, terrain: Terrain);
+
+    /// Stops the tower from attacking for `duration` seconds.
+    fn disable(&mut self, duration: f32);
+
+    fn is_disabled(&self) -> bool;
+
+    fn get_health(&self) -> f32;
+
+    fn get_max_health(&self) -> f32;
+
+    /// Lowers the health of the tower, e.g. when attacked by a monster. The
+    /// board removes towers without any health left.
+    fn take_damage(&mut self, damage: f32);
+
+    /// Tint of the tower sprite, showing whether the tower is disabled or
+    /// damaged. Damaged towers turn redder the less health they have left.
+    fn tint(&self) -> Color {
+        if self.is_disabled() {
+            return DISABLED_COLOR;
+        }
+        let health = (self.get_health() / self.get_max_health()).clamp(0.0, 1.0);
+        Color::new(1.0, 0.4 + 0.6 * health, 0.4 + 0.6 * health, 1.0)
+    }
+
+    /// Whether the tower can hit flying monsters.
+    fn is_anti_air(&self) -> bool;
+
+    /// Pixels around the tower within which stealthy monsters are revealed
+    /// to all towers, `None` for towers without detection.
+    fn detection_range(&self) -> Option<f32>;
+
+    /// Whether the monster can be attacked by the tower at all, regardless
+    /// of range.
+    fn can_target(&self, monster: &dyn Monster) -> bool {
+        (!monster.is_flying() || self.is_anti_air()) && !monster.is_hidden()
+    }
+
+    /// Whether the monster can be attacked by the tower right now.
+    fn can_hit(&self, monster: &dyn Monster) -> bool {
+        self.can_target(monster)
+            && is_in_range(
+                self.get_center_pos_abs(),
+                monster.get_center_pos_abs(),
+                self.attack_range(),
+            )
+    }
+
+    /// Ids of up to `count` monsters the tower attacks according to its
+    /// targeting policy, `current` being the previous (first) target.
+    fn pick_targets(
+        &self,
+        monster_views: &[Box<dyn MonsterView>],
+        current: Option<MonsterId>,
+        count: u32,
+    ) -> Vec<MonsterId> {
+        let mut candidates: Vec<&dyn Monster> = monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .filter(|monster| {
+                monster.get_current_state() != MonsterState::Dead && self.can_hit(*monster)
+            })
+            .collect();
+
+        let mut targets = Vec::new();
+        while targets.len() < count as usize {
+            let target =
+                match self
+                    .get_targeting()
+                    .pick(self.get_center_pos_abs(), &candidates, current)
+                {
+                    Some(target) => target,
+                    None => break,
+                };
+            candidates.retain(|monster| monster.get_id() != target);
+            targets.push(target);
+        }
+        targets
+    }
 }
@@ -0,0 +1,333 @@
+use crate::{
+    game_components::{
+        monsters::StatusEffect,
+        towers::{TowerStats, Upgrade},
+        Damage,
+    },
+    level_system::{read_resource, LevelError},
+};
+
+use ggez::Context;
+
+use serde::Deserialize;
+
+use std::rc::Rc;
+
+/// Name of a tower definition, as shown in the build bar.
+pub type TowerType = String;
+
+/// Keys used by the controls of a selected tower (targeting and selling),
+/// which can't select a tower to place.
+pub const RESERVED_HOTKEYS: [char; 2] = ['t', 's'];
+
+/// Something a tower does besides attacking the monsters in its range.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+pub enum TowerAbility {
+    /// The tower can be placed on water.
+    BuildOnWater,
+    /// The tower can hit flying monsters.
+    AntiAir,
+    /// Reveals stealthy monsters within the attack range of the tower to all
+    /// towers.
+    Detection,
+    /// Applies a status effect to every monster hit directly by a regular
+    /// attack.
+    OnHit { effect: StatusEffect },
+    /// Every `cooldown` seconds, fires a projectile at a random monster
+    /// anywhere on the board, flying at `speed` pixels per second. The first
+    /// strike comes after half the cooldown.
+    Strike {
+        damage: Damage,
+        cooldown: f32,
+        speed: f32,
+    },
+}
+
+/// Properties shared by all towers of one type, as described by an entry in
+/// `resources/towers/towers.ron`. Sizes and positions are in pixels, resource
+/// paths are relative to the resource directory.
+///
+/// Example:
+/// ```ron
+/// (
+///     name: "Basic",
+///     description: "Fires cannonballs that splash nearby monsters.",
+///     cost: 10,
+///     hotkey: '1',
+///     health: 100.0,
+///     icon: "/ui/tower.png",
+///     icon_selected: "/ui/tower_selected.png",
+///     stats: (
+///         attack_range: 100.0,
+///         attack_timer: 1.0,
+///         damage: (amount: 10.0, damage_type: Physical),
+///         projectile: (speed: 250.0, flight: Ballistic, hit_radius: 12.0, sprite: Cannonball),
+///         sprite: "/tower2.png",
+///     ),
+///     abilities: [AntiAir],
+///     upgrades: [(name: "Reinforced", cost: 15, bonus_range: 20.0)],
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TowerDefinition {
+    pub name: TowerType,
+    /// Shown when hovering over the tower in the build bar.
+    pub description: String,
+    /// Gold it costs to place the tower.
+    pub cost: u32,
+    /// Key selecting the tower for placement.
+    pub hotkey: char,
+    pub health: f32,
+    /// Build bar icon, and the icon shown while hovering over it.
+    pub icon: String,
+    pub icon_selected: String,
+    /// Range, attack timer, damage and sprite before any upgrades.
+    pub stats: TowerStats,
+    /// Seconds before a newly placed tower attacks for the first time.
+    #[serde(default)]
+    pub initial_cooldown: f32,
+    #[serde(default)]
+    pub abilities: Vec<TowerAbility>,
+    /// Upgrades available for a newly placed tower.
+    #[serde(default)]
+    pub upgrades: Vec<Upgrade>,
+}
+
+impl TowerDefinition {
+    pub fn can_build_on_water(&self) -> bool {
+        self.abilities.contains(&TowerAbility::BuildOnWater)
+    }
+
+    pub fn is_anti_air(&self) -> bool {
+        self.abilities.contains(&TowerAbility::AntiAir)
+    }
+
+    pub fn has_detection(&self) -> bool {
+        self.abilities.contains(&TowerAbility::Detection)
+    }
+
+    /// Status effects applied by regular attacks.
+    pub fn on_hit(&self) -> Vec<StatusEffect> {
+        self.abilities
+            .iter()
+            .filter_map(|ability| match ability {
+                TowerAbility::OnHit { effect } => Some(*effect),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All upgrades in the upgrade tree of the tower.
+    pub fn all_upgrades(&self) -> impl Iterator<Item = &Upgrade> {
+        self.upgrades.iter().flat_map(|upgrade| upgrade.all())
+    }
+
+    /// Paths of the tower sprite and of every sprite an upgrade switches to.
+    pub fn sprites(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.stats.sprite).chain(
+            self.all_upgrades()
+                .filter_map(|upgrade| upgrade.sprite.as_ref()),
+        )
+    }
+
+    pub fn uses_hotkey(&self, key: char) -> bool {
+        self.hotkey.eq_ignore_ascii_case(&key)
+    }
+}
+
+/// All tower types the player can build, loaded from a tower definition file.
+/// The build bar lists them in the order they are defined in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TowerRegistry {
+    definitions: Vec<Rc<TowerDefinition>>,
+}
+
+impl TowerRegistry {
+    /// Loads and validates a tower definition file from the ggez resource
+    /// directory, e.g. `/towers/towers.ron`.
+    pub fn load(ctx: &mut Context, path: &str) -> Result<TowerRegistry, LevelError> {
+        TowerRegistry::from_ron_str(&read_resource(ctx, path)?)
+    }
+
+    /// Parses and validates a list of tower definitions from its RON
+    /// representation.
+    pub fn from_ron_str(contents: &str) -> Result<TowerRegistry, LevelError> {
+        let definitions: Vec<TowerDefinition> =
+            ron::de::from_str(contents).map_err(|e| LevelError::Parse(e.to_string()))?;
+
+        for (index, definition) in definitions.iter().enumerate() {
+            let invalid = |reason| LevelError::InvalidTower {
+                tower: definition.name.clone(),
+                reason,
+            };
+
+            if definitions[..index]
+                .iter()
+                .any(|other| other.name == definition.name)
+            {
+                return Err(invalid("defined more than once"));
+            }
+            if definitions[..index]
+                .iter()
+                .any(|other| other.uses_hotkey(definition.hotkey))
+                || RESERVED_HOTKEYS
+                    .iter()
+                    .any(|&reserved| definition.uses_hotkey(reserved))
+            {
+                return Err(invalid("hotkey is taken by another tower or control"));
+            }
+            if definition.health <= 0.0 {
+                return Err(invalid("health must be positive"));
+            }
+            let stats = &definition.stats;
+            if stats.attack_range < 0.0
+                || stats.attack_timer <= 0.0
+                || stats.projectile.speed <= 0.0
+                || stats.targets == 0
+            {
+                return Err(invalid(
+                    "attack range may not be negative, attack timer, projectile speed and targets must be positive",
+                ));
+            }
+            if definition
+                .all_upgrades()
+                .any(|upgrade| upgrade.attack_timer_multiplier <= 0.0)
+            {
+                return Err(invalid("upgrade attack timer multipliers must be positive"));
+            }
+            if definition.abilities.iter().any(|ability| match ability {
+                TowerAbility::Strike {
+                    cooldown, speed, ..
+                } => *cooldown <= 0.0 || *speed <= 0.0,
+                _ => false,
+            }) {
+                return Err(invalid("strike cooldown and speed must be positive"));
+            }
+        }
+
+        Ok(TowerRegistry {
+            definitions: definitions.into_iter().map(Rc::new).collect(),
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<TowerDefinition>> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name == name)
+            .cloned()
+    }
+
+    /// The tower selected by pressing `key`, if any.
+    pub fn by_hotkey(&self, key: char) -> Option<Rc<TowerDefinition>> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.uses_hotkey(key))
+            .cloned()
+    }
+
+    pub fn definitions(&self) -> &[Rc<TowerDefinition>] {
+        &self.definitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::towers::{TowerAbility, TowerRegistry};
+    use crate::level_system::LevelError;
+
+    fn tower(name: &str, hotkey: char, extra: &str) -> String {
+        format!(
+            "(name: \"{}\", description: \"\", cost: 10, hotkey: '{}', health: 100.0, \
+              icon: \"/icon.png\", icon_selected: \"/icon_selected.png\", \
+              stats: (attack_range: 100.0, attack_timer: 1.0, \
+                      damage: (amount: 10.0, damage_type: Physical), \
+                      projectile: (speed: 100.0, flight: Homing, hit_radius: 10.0, sprite: Shuriken), \
+                      sprite: \"/tower.png\"){})",
+            name, hotkey, extra
+        )
+    }
+
+    #[test]
+    fn default_towers_are_valid() {
+        let registry =
+            TowerRegistry::from_ron_str(include_str!("../../../resources/towers/towers.ron"))
+                .unwrap();
+
+        let names: Vec<&str> = registry
+            .definitions()
+            .iter()
+            .map(|definition| definition.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Basic", "Ninja"]);
+
+        let basic = registry.get("Basic").unwrap();
+        assert_eq!(basic.cost, 10);
+        assert!(!basic.can_build_on_water());
+        assert_eq!(registry.by_hotkey('1'), Some(basic));
+
+        let ninja = registry.get("Ninja").unwrap();
+        assert_eq!(ninja.cost, 20);
+        assert_eq!(ninja.initial_cooldown, 2.0);
+        assert!(ninja.can_build_on_water() && ninja.is_anti_air() && ninja.has_detection());
+        assert_eq!(ninja.on_hit().len(), 1);
+        assert!(ninja
+            .abilities
+            .iter()
+            .any(|ability| matches!(ability, TowerAbility::Strike { .. })));
+        assert_eq!(ninja.sprites().count(), 3);
+        assert!(registry.get("Wizard").is_none());
+    }
+
+    #[test]
+    fn defaults_of_optional_fields() {
+        let registry =
+            TowerRegistry::from_ron_str(&format!("[{}]", tower("Archer", '3', ""))).unwrap();
+
+        let archer = registry.get("Archer").unwrap();
+        assert_eq!(archer.stats.targets, 1);
+        assert_eq!(archer.initial_cooldown, 0.0);
+        assert!(archer.stats.projectile.splash.is_none());
+        assert!(archer.abilities.is_empty());
+        assert!(archer.upgrades.is_empty());
+        assert!(archer.uses_hotkey('3'));
+    }
+
+    #[test]
+    fn hotkeys_are_unique() {
+        let registry = TowerRegistry::from_ron_str(&format!(
+            "[{}, {}]",
+            tower("Archer", 'a', ""),
+            tower("Mage", 'A', "")
+        ));
+        assert_eq!(
+            registry,
+            Err(LevelError::InvalidTower {
+                tower: "Mage".to_string(),
+                reason: "hotkey is taken by another tower or control"
+            })
+        );
+
+        // Taken by the controls of a selected tower.
+        assert!(TowerRegistry::from_ron_str(&format!("[{}]", tower("Sniper", 's', ""))).is_err());
+    }
+
+    #[test]
+    fn invalid_upgrade() {
+        let registry = TowerRegistry::from_ron_str(&format!(
+            "[{}]",
+            tower(
+                "Archer",
+                '3',
+                ", upgrades: [(name: \"Fast\", cost: 10, next: \
+                   [(name: \"Faster\", cost: 20, attack_timer_multiplier: 0.0)])]"
+            )
+        ));
+        assert_eq!(
+            registry,
+            Err(LevelError::InvalidTower {
+                tower: "Archer".to_string(),
+                reason: "upgrade attack timer multipliers must be positive"
+            })
+        );
+    }
+}
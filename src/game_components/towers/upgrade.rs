@@ -0,0 +1,201 @@
+use crate::game_components::{projectile::ProjectileStats, Damage};
+
+use serde::Deserialize;
+
+fn default_targets() -> u32 {
+    1
+}
+
+fn default_attack_timer_multiplier() -> f32 {
+    1.0
+}
+
+/// Stats of a single tower, starting out as the stats of its type and
+/// changed by each upgrade bought for it.
+///
+/// Example:
+/// ```ron
+/// (
+///     attack_range: 100.0,
+///     attack_timer: 1.0,
+///     damage: (amount: 10.0, damage_type: Physical),
+///     projectile: (speed: 250.0, flight: Ballistic, hit_radius: 12.0, sprite: Cannonball),
+///     targets: 1,
+///     sprite: "/tower2.png",
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TowerStats {
+    /// Pixels, before any terrain bonus.
+    pub attack_range: f32,
+    /// Seconds between attacks.
+    pub attack_timer: f32,
+    pub damage: Damage,
+    pub projectile: ProjectileStats,
+    /// Number of monsters attacked at once.
+    #[serde(default = "default_targets")]
+    pub targets: u32,
+    pub sprite: String,
+}
+
+impl TowerStats {
+    pub fn apply(&mut self, upgrade: &Upgrade) {
+        self.attack_range += upgrade.bonus_range;
+        self.attack_timer *= upgrade.attack_timer_multiplier;
+        self.damage.amount += upgrade.bonus_damage;
+        self.targets += upgrade.extra_targets;
+        if let Some(sprite) = &upgrade.sprite {
+            self.sprite = sprite.clone();
+        }
+    }
+}
+
+/// One level of a tower upgrade tree. Everything but the name and cost is
+/// optional and leaves the stats unchanged if left out.
+///
+/// Example:
+/// ```ron
+/// (
+///     name: "Assassin",
+///     cost: 60,
+///     bonus_damage: 40.0,
+///     attack_timer_multiplier: 1.5,
+///     sprite: Some("/tower_assassin.png"),
+///     next: [(name: "Master Assassin", cost: 90, bonus_range: 30.0)],
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Upgrade {
+    pub name: String,
+    /// Gold.
+    pub cost: u32,
+    /// Pixels.
+    #[serde(default)]
+    pub bonus_range: f32,
+    #[serde(default = "default_attack_timer_multiplier")]
+    pub attack_timer_multiplier: f32,
+    #[serde(default)]
+    pub bonus_damage: f32,
+    #[serde(default)]
+    pub extra_targets: u32,
+    #[serde(default)]
+    pub sprite: Option<String>,
+    /// Upgrades available once this one is bought. More than one is a choice
+    /// between specializations, buying one rules out the others.
+    #[serde(default)]
+    pub next: Vec<Upgrade>,
+}
+
+impl Upgrade {
+    /// This upgrade and all upgrades that can follow it.
+    pub fn all(&self) -> Vec<&Upgrade> {
+        let mut upgrades = vec![self];
+        for next in self.next.iter() {
+            upgrades.extend(next.all());
+        }
+        upgrades
+    }
+}
+
+/// The upgrades bought for a tower and the ones available next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeProgress {
+    pub level: u32,
+    /// Gold paid for all upgrades bought so far.
+    pub spent: u32,
+    pub available: Vec<Upgrade>,
+}
+
+impl UpgradeProgress {
+    pub fn new(tree: Vec<Upgrade>) -> UpgradeProgress {
+        UpgradeProgress {
+            level: 0,
+            spent: 0,
+            available: tree,
+        }
+    }
+
+    /// Buys the available upgrade at index `choice`, returning it. Paying
+    /// for it is up to the caller.
+    pub fn take(&mut self, choice: usize) -> Option<Upgrade> {
+        if choice >= self.available.len() {
+            return None;
+        }
+
+        let mut upgrade = self.available.swap_remove(choice);
+        self.available = std::mem::take(&mut upgrade.next);
+        self.level += 1;
+        self.spent += upgrade.cost;
+        Some(upgrade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{
+        projectile::{Flight, ProjectileSprite, ProjectileStats},
+        towers::upgrade::{TowerStats, Upgrade, UpgradeProgress},
+        Damage, DamageType,
+    };
+
+    fn tree() -> Vec<Upgrade> {
+        ron::de::from_str(
+            "[(name: \"Range\", cost: 10, bonus_range: 10.0, next: [ \
+                (name: \"Heavy\", cost: 20, bonus_damage: 5.0, attack_timer_multiplier: 2.0, \
+                 sprite: Some(\"/heavy.png\")), \
+                (name: \"Spread\", cost: 30, extra_targets: 2)])]",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn upgrades_branch() {
+        let mut progress = UpgradeProgress::new(tree());
+        assert!(progress.take(1).is_none());
+
+        assert_eq!(progress.take(0).unwrap().name, "Range");
+        assert_eq!(progress.level, 1);
+        let names: Vec<&str> = progress.available.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Heavy", "Spread"]);
+
+        assert_eq!(progress.take(1).unwrap().cost, 30);
+        assert_eq!(progress.level, 2);
+        assert_eq!(progress.spent, 40);
+        assert!(progress.available.is_empty());
+    }
+
+    #[test]
+    fn upgrades_change_stats() {
+        let mut stats = TowerStats {
+            attack_range: 100.0,
+            attack_timer: 1.0,
+            damage: Damage::new(10.0, DamageType::Pierce),
+            projectile: ProjectileStats {
+                speed: 100.0,
+                flight: Flight::Homing,
+                hit_radius: 10.0,
+                splash: None,
+                sprite: ProjectileSprite::Shuriken,
+            },
+            targets: 1,
+            sprite: "/tower.png".to_string(),
+        };
+
+        let mut progress = UpgradeProgress::new(tree());
+        stats.apply(&progress.take(0).unwrap());
+        stats.apply(&progress.take(0).unwrap());
+
+        assert_eq!(stats.attack_range, 110.0);
+        assert_eq!(stats.attack_timer, 2.0);
+        assert_eq!(stats.damage, Damage::new(15.0, DamageType::Pierce));
+        assert_eq!(stats.targets, 1);
+        assert_eq!(stats.sprite, "/heavy.png");
+    }
+
+    #[test]
+    fn all_upgrades_of_tree() {
+        let tree = tree();
+        let names: Vec<&str> = tree[0].all().iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Range", "Heavy", "Spread"]);
+    }
+}
@@ -0,0 +1,70 @@
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+pub const HEALTH_BAR_WIDTH: f32 = 24.0;
+pub const HEALTH_BAR_HEIGHT: f32 = 4.0;
+
+/// Draws a small health bar centered just above `top_center` (absolute,
+/// pixels), e.g. the top center of a sprite. Nothing is drawn at full
+/// health. Used by everything that shows its health, so all health bars look
+/// the same.
+pub fn draw_health_bar(
+    ctx: &mut Context,
+    top_center: [f32; 2],
+    health: f32,
+    max_health: f32,
+) -> GameResult {
+    let fraction = match health_fraction(health, max_health) {
+        Some(fraction) => fraction,
+        None => return Ok(()),
+    };
+
+    let location = (Point2 {
+        x: top_center[0] - HEALTH_BAR_WIDTH / 2.0,
+        y: top_center[1] - HEALTH_BAR_HEIGHT - 2.0,
+    },);
+
+    let background = graphics::Mesh::new_rectangle(
+        ctx,
+        graphics::DrawMode::fill(),
+        [0.0, 0.0, HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT].into(),
+        graphics::Color::new(0.0, 0.0, 0.0, 0.7),
+    )?;
+    graphics::draw(ctx, &background, location)?;
+
+    if fraction > 0.0 {
+        // Green at full health, turning red towards death.
+        let bar = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            [0.0, 0.0, HEALTH_BAR_WIDTH * fraction, HEALTH_BAR_HEIGHT].into(),
+            graphics::Color::new(1.0 - fraction, fraction, 0.0, 1.0),
+        )?;
+        graphics::draw(ctx, &bar, location)?;
+    }
+
+    Ok(())
+}
+
+/// Share of the health left, shown by the health bar. `None` at full health,
+/// when no health bar is shown.
+pub fn health_fraction(health: f32, max_health: f32) -> Option<f32> {
+    if health >= max_health || max_health <= 0.0 {
+        return None;
+    }
+    Some((health / max_health).clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_views::health_bar::health_fraction;
+
+    #[test]
+    fn hidden_at_full_health() {
+        assert_eq!(health_fraction(100.0, 100.0), None);
+        assert_eq!(health_fraction(120.0, 100.0), None);
+        assert_eq!(health_fraction(0.0, 0.0), None);
+
+        assert_eq!(health_fraction(25.0, 100.0), Some(0.25));
+        assert_eq!(health_fraction(-5.0, 100.0), Some(0.0));
+    }
+}
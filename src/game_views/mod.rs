@@ -1 +1,2 @@
+pub mod health_bar;
 pub mod monsters;
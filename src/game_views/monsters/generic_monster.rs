@@ -0,0 +1,191 @@
+use crate::{
+    animation_system::Animation,
+    asset_system::AssetManager,
+    game_components::monsters::{
+        AuraKind, GenericMonster, Monster, MonsterDefinition, MonsterState,
+    },
+    game_views::{health_bar::draw_health_bar, monsters::MonsterView},
+    utils::Direction,
+};
+
+use ggez::{
+    graphics::{self, Color, WHITE},
+    mint::Point2,
+    Context, GameResult,
+};
+
+use std::rc::Rc;
+
+/// Responsible for drawing a generic monster to the screen and managing view
+/// related attributes, such as animations, of the monster game component.
+pub struct GenericMonsterView {
+    pub monster: GenericMonster,
+    pub animations: Animation, // note: only have a single animation for now.
+}
+
+impl GenericMonsterView {
+    /// Pixels flying monsters are drawn above their position on the ground,
+    /// which is marked by a shadow.
+    pub const FLIGHT_HEIGHT: f32 = 20.0;
+    pub const HIT_FLASH_COLOR: Color = Color {
+        r: 1.0,
+        g: 0.25,
+        b: 0.25,
+        a: 1.0,
+    };
+
+    pub fn new(
+        definition: Rc<MonsterDefinition>,
+        asset_manager: &AssetManager,
+        position: [f32; 2],
+        route: Vec<[f32; 2]>,
+        lateral_offset: f32,
+    ) -> GenericMonsterView {
+        let sprites = asset_manager
+            .monster_assets
+            .get(&definition.name)
+            .map(|assets| assets.walking_sprites.clone())
+            .unwrap_or_default();
+
+        GenericMonsterView {
+            animations: Animation {
+                current_sprite: 0,
+                next_sprite_interval: definition.animation_interval,
+                next_sprite_time: 0,
+                sprites,
+            },
+            monster: GenericMonster::new(definition, position, route)
+                .with_lateral_offset(lateral_offset),
+        }
+    }
+}
+
+impl MonsterView for GenericMonsterView {
+    fn draw(&mut self, ctx: &mut Context, _asset_manager: &AssetManager) -> GameResult {
+        let sprite = match self.animations.sprites.first() {
+            Some(sprite) => sprite,
+            None => return Ok(()), // Nothing to draw.
+        };
+        let half_width = sprite.width() as f32 / 2.0;
+        let half_height = sprite.height() as f32 / 2.0;
+        let sprite_offset = self.monster.definition.sprite_offset;
+        let mut position = self.monster.get_position();
+        if self.monster.is_flying() {
+            let center = self.monster.get_center_pos_abs();
+            let shadow_size = self.monster.definition.size;
+            let shadow = graphics::Mesh::new_ellipse(
+                ctx,
+                graphics::DrawMode::fill(),
+                Point2 { x: 0.0, y: 0.0 },
+                shadow_size / 2.0,
+                shadow_size / 4.0,
+                0.5,
+                graphics::Color::new(0.0, 0.0, 0.0, 0.3),
+            )?;
+            graphics::draw(
+                ctx,
+                &shadow,
+                (Point2 {
+                    x: center[0],
+                    y: center[1],
+                },),
+            )?;
+
+            position[1] -= GenericMonsterView::FLIGHT_HEIGHT;
+        }
+
+        if self.monster.state == MonsterState::Shielded {
+            let size = self.monster.definition.size;
+            let shield = graphics::Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::fill(),
+                Point2 { x: 0.0, y: 0.0 },
+                size,
+                0.5,
+                graphics::Color::new(0.4, 0.7, 1.0, 0.35),
+            )?;
+            let center = Point2 {
+                x: position[0] + size / 2.0,
+                y: position[1] + size / 2.0,
+            };
+            graphics::draw(ctx, &shield, (center,))?;
+        }
+
+        // Faint ring showing how far the auras of the monster reach.
+        let center = self.monster.get_center_pos_abs();
+        for aura in self.monster.definition.auras.iter() {
+            let color = match aura.kind {
+                AuraKind::Heal => graphics::Color::new(0.3, 1.0, 0.3, 0.4),
+                AuraKind::Haste => graphics::Color::new(1.0, 0.8, 0.2, 0.4),
+                AuraKind::Shield => graphics::Color::new(0.4, 0.7, 1.0, 0.4),
+            };
+            let ring = graphics::Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::stroke(1.5),
+                Point2 { x: 0.0, y: 0.0 },
+                aura.range,
+                0.5,
+                color,
+            )?;
+            graphics::draw(
+                ctx,
+                &ring,
+                (Point2 {
+                    x: center[0],
+                    y: center[1],
+                },),
+            )?;
+        }
+
+        // Hits flash the sprite, active status effects are shown by tinting
+        // it.
+        let mut color = if self.monster.hit_flash_time > 0.0 {
+            GenericMonsterView::HIT_FLASH_COLOR
+        } else {
+            self.monster.status_effects.tint().unwrap_or(WHITE)
+        };
+        // Stealthy monsters are see-through, more so when not detected.
+        if self.monster.is_stealthy() {
+            color.a = if self.monster.is_hidden() { 0.35 } else { 0.7 };
+        }
+
+        if self.monster.direction == Direction::Left {
+            // Flipping along y-axis causes image to end up at a position
+            // (-width, 0). Offsetting with (+width/2, -height/2) makes the
+            // image center end up at (0,0).
+            let offset_position = Point2 {
+                x: position[0] + half_width,
+                y: position[1] - half_height + sprite_offset[1],
+            };
+
+            // Flip along y-axis. Scale then move.
+            self.animations
+                .draw(ctx, self.monster.direction, offset_position, color)?;
+        } else {
+            let offset_position = Point2 {
+                x: position[0] - half_width + sprite_offset[0],
+                y: position[1] - half_height + sprite_offset[1],
+            };
+            self.animations
+                .draw(ctx, self.monster.direction, offset_position, color)?;
+        }
+
+        let sprite_top = position[1] - half_height + sprite_offset[1];
+        draw_health_bar(
+            ctx,
+            [self.monster.get_center_pos_abs()[0], sprite_top],
+            self.monster.get_health(),
+            self.monster.get_max_health(),
+        )?;
+
+        Ok(())
+    }
+
+    fn get_monster_mut(&mut self) -> &mut dyn Monster {
+        &mut self.monster
+    }
+
+    fn get_monster(&self) -> &dyn Monster {
+        &self.monster
+    }
+}
@@ -1,7 +1,5 @@
-pub mod chicken;
-pub mod cool_chicken;
+pub mod generic_monster;
 pub mod monster;
 
-pub use self::chicken::ChickenView;
-pub use self::cool_chicken::CoolChickenView;
+pub use self::generic_monster::GenericMonsterView;
 pub use self::monster::MonsterView;
@@ -0,0 +1,178 @@
+use crate::game_components::{BoardMode, Terrain};
+
+use std::fmt;
+
+/// Errors that can occur while loading level data (maps, wave schedules and
+/// monster definitions) from resource files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelError {
+    /// The file could not be opened or read.
+    Io(String),
+    /// The file is not valid RON or does not match the expected format.
+    Parse(String),
+    /// The map describes an empty grid.
+    InvalidGridSize { width: i32, height: i32 },
+    /// The map does not contain any routes.
+    EmptyPath,
+    /// A maze mode map does not have any entrances.
+    NoEntrances,
+    /// The map sets a field its mode doesn't use, e.g. routes in maze mode.
+    UnusedField {
+        field: &'static str,
+        mode: BoardMode,
+    },
+    /// The base can't be reached from an entrance of a maze mode map.
+    BaseUnreachable { entrance: [i32; 2] },
+    /// One of the routes does not contain any path blocks.
+    EmptyRoute { route: usize },
+    /// A position lies outside of the map grid.
+    OutOfBounds {
+        what: &'static str,
+        position: [i32; 2],
+    },
+    /// The same block appears more than once in the path.
+    DuplicatePathBlock {
+        route: usize,
+        index: usize,
+        position: [i32; 2],
+    },
+    /// Two consecutive path blocks are not next to each other.
+    PathNotContiguous {
+        route: usize,
+        index: usize,
+        from: [i32; 2],
+        to: [i32; 2],
+    },
+    /// The base covers one of the path blocks.
+    BaseOverlapsPath { position: [i32; 2] },
+    /// The base is not next to the last block of a route.
+    BaseNotAtPathEnd {
+        route: usize,
+        base: [i32; 2],
+        path_end: [i32; 2],
+    },
+    /// A route block or entrance lies on terrain monsters can't walk on.
+    UnwalkableTerrain {
+        position: [i32; 2],
+        terrain: Terrain,
+    },
+    /// An unbuildable cell is placed on top of the path or base.
+    UnbuildableOverlap { position: [i32; 2] },
+    /// An obstacle is placed on top of the path, base, an unbuildable cell
+    /// or another obstacle.
+    ObstacleOverlap { position: [i32; 2] },
+    /// The share of the invested gold refunded for sold towers is not
+    /// between 0 and 1.
+    InvalidSellRefund { sell_refund: f32 },
+    /// A wave schedule entry spawns earlier than the entry before it.
+    ScheduleNotChronological { index: usize },
+    /// A wave schedule entry refers to an entrance the map doesn't have.
+    InvalidEntrance { index: usize, entrance: usize },
+    /// A wave schedule entry spawns a monster that isn't defined.
+    UnknownMonster { index: usize, monster: String },
+    /// A monster definition is not usable, e.g. it has no sprites.
+    InvalidMonster {
+        monster: String,
+        reason: &'static str,
+    },
+    /// A tower definition is not usable, e.g. its hotkey is taken.
+    InvalidTower { tower: String, reason: &'static str },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Io(error) => write!(f, "could not read level file: {}", error),
+            LevelError::Parse(error) => write!(f, "could not parse level file: {}", error),
+            LevelError::InvalidGridSize { width, height } => {
+                write!(f, "invalid grid size {}x{}", width, height)
+            }
+            LevelError::EmptyPath => write!(f, "map has no routes"),
+            LevelError::NoEntrances => write!(f, "maze map has no entrances"),
+            LevelError::UnusedField { field, mode } => {
+                write!(f, "{:?} mode maps don't use {}", mode, field)
+            }
+            LevelError::BaseUnreachable { entrance } => {
+                write!(f, "base can't be reached from entrance {:?}", entrance)
+            }
+            LevelError::EmptyRoute { route } => write!(f, "route {} has no path blocks", route),
+            LevelError::OutOfBounds { what, position } => {
+                write!(f, "{} at {:?} is outside of the grid", what, position)
+            }
+            LevelError::DuplicatePathBlock {
+                route,
+                index,
+                position,
+            } => write!(
+                f,
+                "route {}: path block {} at {:?} is a duplicate of an earlier path block",
+                route, index, position
+            ),
+            LevelError::PathNotContiguous {
+                route,
+                index,
+                from,
+                to,
+            } => write!(
+                f,
+                "route {}: path is not contiguous, block {} at {:?} is not next to {:?}",
+                route, index, to, from
+            ),
+            LevelError::BaseOverlapsPath { position } => {
+                write!(f, "base covers path block at {:?}", position)
+            }
+            LevelError::BaseNotAtPathEnd {
+                route,
+                base,
+                path_end,
+            } => write!(
+                f,
+                "route {}: base at {:?} is not next to the end of the path at {:?}",
+                route, base, path_end
+            ),
+            LevelError::UnwalkableTerrain { position, terrain } => write!(
+                f,
+                "path block or entrance at {:?} is on {:?}, which monsters can't walk on",
+                position, terrain
+            ),
+            LevelError::UnbuildableOverlap { position } => write!(
+                f,
+                "unbuildable cell at {:?} overlaps the path or base",
+                position
+            ),
+            LevelError::ObstacleOverlap { position } => write!(
+                f,
+                "obstacle at {:?} overlaps the path, base or another obstacle",
+                position
+            ),
+            LevelError::InvalidSellRefund { sell_refund } => write!(
+                f,
+                "sell refund {} is not a share between 0 and 1",
+                sell_refund
+            ),
+            LevelError::ScheduleNotChronological { index } => write!(
+                f,
+                "wave schedule entry {} spawns before the entry preceding it",
+                index
+            ),
+            LevelError::InvalidEntrance { index, entrance } => write!(
+                f,
+                "wave schedule entry {} spawns at entrance {} which the map does not have",
+                index, entrance
+            ),
+            LevelError::UnknownMonster { index, monster } => write!(
+                f,
+                "wave schedule entry {} spawns unknown monster {:?}",
+                index, monster
+            ),
+            LevelError::InvalidMonster { monster, reason } => {
+                write!(f, "monster {:?} {}", monster, reason)
+            }
+            LevelError::InvalidTower { tower, reason } => {
+                write!(f, "tower {:?} {}", tower, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
@@ -0,0 +1,507 @@
+use crate::{
+    game_components::{
+        board::DEFAULT_SELL_REFUND, pathfinding::find_path, Base, BoardMode, Grid,
+        ObstacleDefinition, Occupant, Terrain, TerrainArea,
+    },
+    level_system::{read_resource, LevelError},
+};
+
+use ggez::Context;
+
+use serde::Deserialize;
+
+/// Level layout as described by a map file in `resources/maps/`. Positions
+/// are given in blocks, the base position is the top left block of the base.
+///
+/// Every route is a path from an entrance to the base. Routes may share
+/// blocks, which is how forks (shared start) and merges (shared end) are
+/// described. Routes starting at the same block share an entrance.
+///
+/// Maps in `Maze` mode have no routes, only `entrances`. Monsters find their
+/// own way from the entrances to the base around the towers. Path mode maps
+/// may not set `entrances`, their entrances are the starts of the routes.
+///
+/// Blocks are grass unless covered by one of the `terrain` areas, later areas
+/// override earlier ones. Route blocks are path, or mud if an area says so,
+/// and may not be rock or water.
+///
+/// `obstacles` block tower placement (and monsters in maze mode) until the
+/// player pays to clear them.
+///
+/// Sold towers refund `sell_refund` of the gold invested in them, unless
+/// they are sold during the build phase they were placed in.
+///
+/// Example:
+/// ```ron
+/// (
+///     width: 22,
+///     height: 12,
+///     routes: [
+///         [(0, 0), (0, 1), (1, 1)],
+///         [(0, 0), (1, 0), (1, 1)],
+///     ],
+///     base: (2, 1),
+///     unbuildable: [(5, 5)],
+///     terrain: [
+///         (terrain: Water, from: (3, 4), to: (5, 6)),
+///         (terrain: MudPath, from: (0, 1), to: (1, 1)),
+///     ],
+///     obstacles: [(kind: Tree, position: (6, 2), gold_drop: 20)],
+///     sell_refund: 0.5,
+///     wave_schedule: "/waves/level_1.ron",
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MapDefinition {
+    #[serde(default)]
+    pub mode: BoardMode,
+    pub width: i32,
+    pub height: i32,
+    #[serde(default)]
+    pub routes: Vec<Vec<[i32; 2]>>,
+    /// Entrances of a maze mode map.
+    #[serde(default)]
+    pub entrances: Vec<[i32; 2]>,
+    pub base: [i32; 2],
+    #[serde(default)]
+    pub unbuildable: Vec<[i32; 2]>,
+    #[serde(default)]
+    pub terrain: Vec<TerrainArea>,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleDefinition>,
+    /// Share (0.0 - 1.0) of the invested gold refunded for a sold tower.
+    #[serde(default = "default_sell_refund")]
+    pub sell_refund: f32,
+    /// Resource path of the wave schedule played on this map.
+    pub wave_schedule: String,
+}
+
+impl MapDefinition {
+    /// Loads and validates a map file from the ggez resource directory, e.g.
+    /// `/maps/level_1.ron`.
+    pub fn load(ctx: &mut Context, path: &str) -> Result<MapDefinition, LevelError> {
+        MapDefinition::from_ron_str(&read_resource(ctx, path)?)
+    }
+
+    /// Parses and validates a map from its RON representation.
+    pub fn from_ron_str(contents: &str) -> Result<MapDefinition, LevelError> {
+        let map: MapDefinition =
+            ron::de::from_str(contents).map_err(|e| LevelError::Parse(e.to_string()))?;
+        map.validate()?;
+        Ok(map)
+    }
+
+    pub fn validate(&self) -> Result<(), LevelError> {
+        if self.width <= 0 || self.height <= 0 {
+            return Err(LevelError::InvalidGridSize {
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let base_blocks = Base::blocks_covered_at(self.base);
+        for &block in base_blocks.iter() {
+            self.check_in_bounds("base", block)?;
+        }
+
+        for area in self.terrain.iter() {
+            self.check_in_bounds("terrain area", area.from)?;
+            self.check_in_bounds("terrain area", area.to)?;
+        }
+
+        match self.mode {
+            BoardMode::Path => self.validate_routes(&base_blocks)?,
+            BoardMode::Maze => self.validate_entrances(&base_blocks)?,
+        }
+
+        for &position in self.unbuildable.iter() {
+            self.check_in_bounds("unbuildable cell", position)?;
+
+            let on_path = self.routes.iter().any(|path| path.contains(&position));
+            if on_path || self.entrances.contains(&position) || base_blocks.contains(&position) {
+                return Err(LevelError::UnbuildableOverlap { position });
+            }
+        }
+
+        for (index, obstacle) in self.obstacles.iter().enumerate() {
+            let position = obstacle.position;
+            self.check_in_bounds("obstacle", position)?;
+
+            let on_path = self.routes.iter().any(|path| path.contains(&position));
+            let taken = on_path
+                || self.entrances.contains(&position)
+                || base_blocks.contains(&position)
+                || self.unbuildable.contains(&position)
+                || self.obstacles[..index]
+                    .iter()
+                    .any(|other| other.position == position);
+            if taken {
+                return Err(LevelError::ObstacleOverlap { position });
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.sell_refund) {
+            return Err(LevelError::InvalidSellRefund {
+                sell_refund: self.sell_refund,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn validate_routes(&self, base_blocks: &[[i32; 2]]) -> Result<(), LevelError> {
+        if !self.entrances.is_empty() {
+            return Err(LevelError::UnusedField {
+                field: "entrances",
+                mode: self.mode,
+            });
+        }
+
+        if self.routes.is_empty() {
+            return Err(LevelError::EmptyPath);
+        }
+
+        for (route, path) in self.routes.iter().enumerate() {
+            if path.is_empty() {
+                return Err(LevelError::EmptyRoute { route });
+            }
+
+            for (index, &position) in path.iter().enumerate() {
+                self.check_in_bounds("path block", position)?;
+                self.check_walkable(position)?;
+
+                if path[..index].contains(&position) {
+                    return Err(LevelError::DuplicatePathBlock {
+                        route,
+                        index,
+                        position,
+                    });
+                }
+
+                if index > 0 {
+                    let from = path[index - 1];
+                    if !is_adjacent(from, position) {
+                        return Err(LevelError::PathNotContiguous {
+                            route,
+                            index,
+                            from,
+                            to: position,
+                        });
+                    }
+                }
+
+                if base_blocks.contains(&position) {
+                    return Err(LevelError::BaseOverlapsPath { position });
+                }
+            }
+
+            let path_end = path[path.len() - 1];
+            if !base_blocks
+                .iter()
+                .any(|&block| is_adjacent(block, path_end))
+            {
+                return Err(LevelError::BaseNotAtPathEnd {
+                    route,
+                    base: self.base,
+                    path_end,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_entrances(&self, base_blocks: &[[i32; 2]]) -> Result<(), LevelError> {
+        if !self.routes.is_empty() {
+            return Err(LevelError::UnusedField {
+                field: "routes",
+                mode: self.mode,
+            });
+        }
+
+        if self.entrances.is_empty() {
+            return Err(LevelError::NoEntrances);
+        }
+
+        let mut grid = Grid::new(self.width, self.height);
+        for area in self.terrain.iter() {
+            for block in area.blocks() {
+                grid.set_terrain(block, area.terrain);
+            }
+        }
+        for &block in base_blocks.iter() {
+            grid.set(block, Occupant::Base);
+        }
+        for &block in self.unbuildable.iter() {
+            grid.set(block, Occupant::Unbuildable);
+        }
+        for obstacle in self.obstacles.iter() {
+            grid.set(obstacle.position, Occupant::Obstacle);
+        }
+
+        let is_walkable = |block| grid.is_passable(block) && grid.terrain(block).is_walkable();
+
+        let goals: Vec<[i32; 2]> = base_blocks
+            .iter()
+            .flat_map(|&block| grid.neighbors(block))
+            .filter(|&block| is_walkable(block))
+            .collect();
+
+        for &entrance in self.entrances.iter() {
+            self.check_in_bounds("entrance", entrance)?;
+            self.check_walkable(entrance)?;
+
+            if base_blocks.contains(&entrance) {
+                return Err(LevelError::BaseOverlapsPath { position: entrance });
+            }
+
+            let reachable = find_path(&grid, entrance, &goals, is_walkable).is_some();
+            if !reachable {
+                return Err(LevelError::BaseUnreachable { entrance });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Terrain of the block as described by the terrain areas, ignoring that
+    /// route blocks become path.
+    fn terrain_of(&self, position: [i32; 2]) -> Terrain {
+        self.terrain
+            .iter()
+            .rev()
+            .find(|area| area.blocks().contains(&position))
+            .map_or(Terrain::Grass, |area| area.terrain)
+    }
+
+    fn check_walkable(&self, position: [i32; 2]) -> Result<(), LevelError> {
+        let terrain = self.terrain_of(position);
+        if !terrain.is_walkable() {
+            return Err(LevelError::UnwalkableTerrain { position, terrain });
+        }
+        Ok(())
+    }
+
+    fn is_in_bounds(&self, position: [i32; 2]) -> bool {
+        position[0] >= 0
+            && position[0] < self.width
+            && position[1] >= 0
+            && position[1] < self.height
+    }
+
+    fn check_in_bounds(&self, what: &'static str, position: [i32; 2]) -> Result<(), LevelError> {
+        if !self.is_in_bounds(position) {
+            return Err(LevelError::OutOfBounds { what, position });
+        }
+        Ok(())
+    }
+}
+
+fn default_sell_refund() -> f32 {
+    DEFAULT_SELL_REFUND
+}
+
+fn is_adjacent(a: [i32; 2], b: [i32; 2]) -> bool {
+    (a[0] - b[0]).abs() + (a[1] - b[1]).abs() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_components::{BoardMode, Terrain};
+    use crate::level_system::{LevelError, MapDefinition};
+
+    fn map_with_path(path: &str, base: &str) -> String {
+        format!(
+            "(width: 10, height: 10, routes: [[{}]], base: {}, wave_schedule: \"/waves/test.ron\")",
+            path, base
+        )
+    }
+
+    #[test]
+    fn default_map_is_valid() {
+        let map = MapDefinition::from_ron_str(include_str!("../../resources/maps/level_1.ron"));
+        assert!(map.is_ok(), "{:?}", map);
+    }
+
+    #[test]
+    fn branching_map_is_valid() {
+        let map = MapDefinition::from_ron_str(include_str!("../../resources/maps/level_2.ron"));
+        assert!(map.is_ok(), "{:?}", map);
+    }
+
+    #[test]
+    fn routes_may_share_blocks() {
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, \
+              routes: [[(0, 0), (0, 1), (1, 1)], [(0, 0), (1, 0), (1, 1)], [(4, 3), (4, 2)]], \
+              base: (2, 1), wave_schedule: \"/waves/test.ron\")",
+        );
+        assert!(map.is_ok(), "{:?}", map);
+    }
+
+    #[test]
+    fn maze_map_is_valid() {
+        let map = MapDefinition::from_ron_str(include_str!("../../resources/maps/maze_1.ron"));
+        assert!(map.is_ok(), "{:?}", map);
+    }
+
+    #[test]
+    fn maze_map_without_entrances() {
+        let map = MapDefinition::from_ron_str(
+            "(mode: Maze, width: 10, height: 10, base: (2, 1), wave_schedule: \"/waves/test.ron\")",
+        );
+        assert_eq!(map, Err(LevelError::NoEntrances));
+    }
+
+    #[test]
+    fn map_fields_of_the_other_mode() {
+        let map = MapDefinition::from_ron_str(
+            "(mode: Maze, width: 10, height: 10, entrances: [(0, 0)], routes: [[(0, 0)]], \
+              base: (2, 1), wave_schedule: \"/waves/test.ron\")",
+        );
+        assert_eq!(
+            map,
+            Err(LevelError::UnusedField {
+                field: "routes",
+                mode: BoardMode::Maze
+            })
+        );
+
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1)]], entrances: [(0, 0)], \
+              base: (1, 1), wave_schedule: \"/waves/test.ron\")",
+        );
+        assert_eq!(
+            map,
+            Err(LevelError::UnusedField {
+                field: "entrances",
+                mode: BoardMode::Path
+            })
+        );
+    }
+
+    #[test]
+    fn parse_valid_map() {
+        let map = MapDefinition::from_ron_str(&map_with_path("(0, 0), (0, 1), (1, 1)", "(2, 0)"))
+            .unwrap();
+
+        assert_eq!(map.routes, vec![vec![[0, 0], [0, 1], [1, 1]]]);
+        assert_eq!(map.base, [2, 0]);
+        assert!(map.unbuildable.is_empty());
+        assert_eq!(map.sell_refund, 0.7);
+        assert_eq!(map.wave_schedule, "/waves/test.ron");
+    }
+
+    #[test]
+    fn invalid_sell_refund() {
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1)]], base: (1, 1), \
+              sell_refund: 1.5, wave_schedule: \"/waves/test.ron\")",
+        );
+        assert_eq!(map, Err(LevelError::InvalidSellRefund { sell_refund: 1.5 }));
+    }
+
+    #[test]
+    fn duplicate_path_block() {
+        let map =
+            MapDefinition::from_ron_str(&map_with_path("(2, 2), (2, 3), (2, 3), (2, 4)", "(3, 4)"));
+
+        assert_eq!(
+            map,
+            Err(LevelError::DuplicatePathBlock {
+                route: 0,
+                index: 2,
+                position: [2, 3]
+            })
+        );
+    }
+
+    #[test]
+    fn path_not_contiguous() {
+        let map = MapDefinition::from_ron_str(&map_with_path("(0, 0), (0, 1), (2, 1)", "(3, 1)"));
+
+        assert_eq!(
+            map,
+            Err(LevelError::PathNotContiguous {
+                route: 0,
+                index: 2,
+                from: [0, 1],
+                to: [2, 1]
+            })
+        );
+    }
+
+    #[test]
+    fn base_not_at_path_end() {
+        let map = MapDefinition::from_ron_str(&map_with_path("(0, 0), (0, 1), (0, 2)", "(5, 5)"));
+
+        assert_eq!(
+            map,
+            Err(LevelError::BaseNotAtPathEnd {
+                route: 0,
+                base: [5, 5],
+                path_end: [0, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn terrain_areas() {
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1), (1, 1)]], base: (2, 0), \
+              terrain: [(terrain: MudPath, from: (0, 0), to: (0, 1)), \
+                        (terrain: Water, from: (5, 5), to: (4, 6))], \
+              wave_schedule: \"/waves/test.ron\")",
+        )
+        .unwrap();
+
+        assert_eq!(map.terrain.len(), 2);
+        assert_eq!(map.terrain[1].blocks().len(), 4);
+    }
+
+    #[test]
+    fn route_through_water() {
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1), (1, 1)]], base: (2, 0), \
+              terrain: [(terrain: Water, from: (0, 1), to: (3, 3))], \
+              wave_schedule: \"/waves/test.ron\")",
+        );
+
+        assert_eq!(
+            map,
+            Err(LevelError::UnwalkableTerrain {
+                position: [0, 1],
+                terrain: Terrain::Water
+            })
+        );
+    }
+
+    #[test]
+    fn maze_entrance_walled_in_by_rock() {
+        let map = MapDefinition::from_ron_str(
+            "(mode: Maze, width: 10, height: 10, entrances: [(0, 0)], base: (5, 5), \
+              terrain: [(terrain: Rock, from: (1, 0), to: (1, 1)), \
+                        (terrain: Rock, from: (0, 1), to: (0, 1))], \
+              wave_schedule: \"/waves/test.ron\")",
+        );
+
+        assert_eq!(map, Err(LevelError::BaseUnreachable { entrance: [0, 0] }));
+    }
+
+    #[test]
+    fn obstacle_on_path() {
+        let map = MapDefinition::from_ron_str(
+            "(width: 10, height: 10, routes: [[(0, 0), (0, 1), (1, 1)]], base: (2, 0), \
+              obstacles: [(kind: Tree, position: (5, 5)), (kind: Rock, position: (0, 1))], \
+              wave_schedule: \"/waves/test.ron\")",
+        );
+
+        assert_eq!(map, Err(LevelError::ObstacleOverlap { position: [0, 1] }));
+    }
+
+    #[test]
+    fn malformed_file() {
+        let map = MapDefinition::from_ron_str("(width: 10, height: 10, routes: [[(0, 0)]]");
+        assert!(matches!(map, Err(LevelError::Parse(_))));
+    }
+}
@@ -1 +1,8 @@
+pub mod level_error;
+pub mod map;
 pub mod monster_spawner;
+pub mod resource;
+
+pub use self::level_error::LevelError;
+pub use self::map::MapDefinition;
+pub use self::resource::read_resource;
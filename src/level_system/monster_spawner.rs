@@ -1,60 +1,410 @@
 use crate::asset_system::AssetManager;
 use crate::game_components::{
     board::Board,
-    monsters::{cool_chicken::CoolChicken, monster::MonsterType},
+    monsters::{MonsterRegistry, MonsterType, MovementMode},
+    BLOCK_SIZE,
 };
-use crate::game_views::monsters::{ChickenView, CoolChickenView};
+use crate::game_views::monsters::GenericMonsterView;
+use crate::level_system::{read_resource, LevelError};
+
+use ggez::Context;
+
+use rand::{thread_rng, Rng};
+
+use serde::Deserialize;
+
+/// A single scheduled monster spawn.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SpawnEntry {
+    /// Name of the monster definition to spawn.
+    pub monster: MonsterType,
+    /// Seconds since the level started. A spawn at least
+    /// `MonsterSpawner::WAVE_GAP` seconds after the previous one starts a new
+    /// wave.
+    pub time: f32,
+    /// Index of the board entrance to spawn at. When not set the spawner
+    /// cycles through all entrances.
+    #[serde(default)]
+    pub entrance: Option<usize>,
+}
 
 pub struct MonsterSpawner {
-    pub spawn_schedule: Vec<(MonsterType, f32)>,
+    pub spawn_schedule: Vec<SpawnEntry>,
     pub elapsed_time: f32,
+    next_entrance: usize,
+    /// Number of waves that started spawning.
+    wave: usize,
+    /// Schedule time of the last spawn, if any.
+    last_spawn_time: Option<f32>,
 }
 
 impl MonsterSpawner {
+    /// Seconds between two spawns that separate two waves. The player gets
+    /// a build phase in between, once the earlier wave has been cleared.
+    pub const WAVE_GAP: f32 = 3.5;
+
     pub fn new() -> MonsterSpawner {
         let spawn_schedule = vec![
-            (MonsterType::Chicken, 0.0),
-            (MonsterType::Chicken, 3.0),
-            (MonsterType::Chicken, 5.0),
-            (MonsterType::Chicken, 7.0),
-            (MonsterType::Chicken, 8.0),
-            (MonsterType::Chicken, 8.5),
-            (MonsterType::Chicken, 8.6),
-            (MonsterType::Chicken, 8.7),
-            (MonsterType::Chicken, 8.8),
-            (MonsterType::CoolChicken, 14.0),
-        ];
+            ("Chicken", 0.0),
+            ("Chicken", 3.0),
+            ("Chicken", 5.0),
+            ("Chicken", 7.0),
+            ("Chicken", 8.0),
+            ("Chicken", 8.5),
+            ("Chicken", 8.6),
+            ("Chicken", 8.7),
+            ("Chicken", 8.8),
+            ("CoolChicken", 14.0),
+        ]
+        .into_iter()
+        .map(|(monster, time)| SpawnEntry {
+            monster: monster.to_string(),
+            time,
+            entrance: None,
+        })
+        .collect();
+
         MonsterSpawner {
             spawn_schedule,
             elapsed_time: 0.0,
+            next_entrance: 0,
+            wave: 0,
+            last_spawn_time: None,
+        }
+    }
+
+    /// Loads a wave schedule file from the ggez resource directory, e.g.
+    /// `/waves/level_1.ron`.
+    pub fn load(ctx: &mut Context, path: &str) -> Result<MonsterSpawner, LevelError> {
+        MonsterSpawner::from_ron_str(&read_resource(ctx, path)?)
+    }
+
+    /// Parses a wave schedule, a chronological list of spawn entries, from
+    /// its RON representation. Waves aren't listed explicitly, a gap of at
+    /// least `WAVE_GAP` seconds between two spawns starts a new one.
+    pub fn from_ron_str(contents: &str) -> Result<MonsterSpawner, LevelError> {
+        let spawn_schedule: Vec<SpawnEntry> =
+            ron::de::from_str(contents).map_err(|e| LevelError::Parse(e.to_string()))?;
+
+        for (index, pair) in spawn_schedule.windows(2).enumerate() {
+            if pair[1].time < pair[0].time {
+                return Err(LevelError::ScheduleNotChronological { index: index + 1 });
+            }
+        }
+
+        Ok(MonsterSpawner {
+            spawn_schedule,
+            elapsed_time: 0.0,
+            next_entrance: 0,
+            wave: 0,
+            last_spawn_time: None,
+        })
+    }
+
+    /// Checks that every entrance referenced by the schedule exists on a
+    /// board with `entrance_count` entrances.
+    pub fn validate_entrances(&self, entrance_count: usize) -> Result<(), LevelError> {
+        for (index, entry) in self.spawn_schedule.iter().enumerate() {
+            if let Some(entrance) = entry.entrance {
+                if entrance >= entrance_count {
+                    return Err(LevelError::InvalidEntrance { index, entrance });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every monster spawned by the schedule is defined in the
+    /// registry.
+    pub fn validate_monsters(&self, monster_registry: &MonsterRegistry) -> Result<(), LevelError> {
+        for (index, entry) in self.spawn_schedule.iter().enumerate() {
+            if monster_registry.get(&entry.monster).is_none() {
+                return Err(LevelError::UnknownMonster {
+                    index,
+                    monster: entry.monster.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the last wave spawned completely and the next one, if any,
+    /// hasn't started yet.
+    pub fn is_wave_spawned(&self) -> bool {
+        match self.spawn_schedule.first() {
+            Some(next) => self.starts_wave(next.time),
+            None => true,
         }
     }
 
+    /// Whether a spawn at `time` starts a new wave.
+    fn starts_wave(&self, time: f32) -> bool {
+        self.last_spawn_time
+            .is_none_or(|last| time - last >= MonsterSpawner::WAVE_GAP)
+    }
+
+    /// Records a spawn at `time`, returning whether it started a new wave.
+    fn record_spawn(&mut self, time: f32) -> bool {
+        let starts_wave = self.starts_wave(time);
+        if starts_wave {
+            self.wave += 1;
+        }
+        self.last_spawn_time = Some(time);
+        starts_wave
+    }
+
+    /// Starts a build phase on the board once the current wave spawned
+    /// completely and none of its monsters are left alive.
+    pub fn update_phase(&self, board: &mut Board) {
+        if self.is_wave_spawned() && !board.has_live_monsters() {
+            board.start_build_phase();
+        }
+    }
+
+    /// Spawns the monsters that are due, and tells the board when a wave or
+    /// a build phase starts.
     pub fn update(
         &mut self,
         elapsed: f32,
         board: &mut Board,
-        asset_manager: &AssetManager
+        asset_manager: &AssetManager,
+        monster_registry: &MonsterRegistry,
     ) {
         self.elapsed_time += elapsed;
 
-        for i in 0..self.spawn_schedule.len() {
-            if self.spawn_schedule[i].1 < self.elapsed_time {
-                if self.spawn_schedule[i].0 == MonsterType::Chicken {
-                    board.monster_views.push(Box::new(ChickenView::new(&asset_manager)));
-                } else if self.spawn_schedule[i].0 == MonsterType::CoolChicken {
-                    board.monster_views.push(Box::new(CoolChickenView {
-                        cool_chicken: CoolChicken::new(),
-                    }));
+        // Schedule is cronological, only the first entries can be due.
+        let due = self
+            .spawn_schedule
+            .iter()
+            .take_while(|entry| entry.time < self.elapsed_time)
+            .count();
+
+        for entry in self.spawn_schedule.drain(..due).collect::<Vec<_>>() {
+            if self.record_spawn(entry.time) {
+                info!("update: wave {} started.", self.wave);
+                board.start_wave();
+            }
+
+            let entrance = match entry.entrance {
+                Some(entrance) => entrance,
+                None => {
+                    let entrance = self.next_entrance % board.spawn_points().len().max(1);
+                    self.next_entrance = entrance + 1;
+                    entrance
+                }
+            };
+
+            let definition = match monster_registry.get(&entry.monster) {
+                Some(definition) => definition,
+                None => {
+                    warn!("update: unknown monster {:?}, skipping.", entry.monster);
+                    continue;
+                }
+            };
+
+            let route = match definition.movement {
+                MovementMode::Walking => board.pick_route(entrance),
+                MovementMode::Flying => board.flight_route(entrance),
+            };
+            let route = match route {
+                Some(route) => route,
+                None => {
+                    warn!(
+                        "update: board has no route for entrance {}, skipping {:?}.",
+                        entrance, entry.monster
+                    );
+                    continue;
                 }
+            };
 
-                if i == self.spawn_schedule.len() - 1 {
-                    self.spawn_schedule = vec![];
+            // Spread out sideways, but kept within the lane.
+            let max_jitter = ((BLOCK_SIZE - definition.size) / 2.0).max(0.0);
+            let jitter = thread_rng().gen_range(-max_jitter..=max_jitter);
+            let position = match Board::spawn_position(&route, jitter) {
+                Some(position) => position,
+                None => {
+                    warn!(
+                        "update: route of entrance {} is empty, skipping {:?}.",
+                        entrance, entry.monster
+                    );
+                    continue;
                 }
-            } else {
-                self.spawn_schedule = self.spawn_schedule.split_off(i);
-                break; // Schedule is cronological, no reason to check further.
+            };
+            board.add_monster(Box::new(GenericMonsterView::new(
+                definition,
+                asset_manager,
+                position,
+                route,
+                jitter,
+            )));
+        }
+
+        self.update_phase(board);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animation_system::Animation;
+    use crate::game_components::{
+        monsters::{GenericMonster, MonsterDefinition, MonsterRegistry, MonsterState},
+        towers::{GenericTower, TowerRegistry},
+        Board,
+    };
+    use crate::game_views::monsters::GenericMonsterView;
+    use crate::level_system::{monster_spawner::MonsterSpawner, LevelError};
+
+    fn default_monsters() -> MonsterRegistry {
+        MonsterRegistry::from_ron_str(include_str!("../../resources/monsters/monsters.ron"))
+            .unwrap()
+    }
+
+    #[test]
+    fn default_wave_schedule_is_valid() {
+        let spawner =
+            MonsterSpawner::from_ron_str(include_str!("../../resources/waves/level_1.ron"))
+                .unwrap();
+        assert_eq!(spawner.spawn_schedule[0].monster, "Chicken");
+        assert_eq!(spawner.spawn_schedule[0].time, 0.0);
+        assert_eq!(spawner.validate_entrances(1), Ok(()));
+        assert_eq!(spawner.validate_monsters(&default_monsters()), Ok(()));
+    }
+
+    #[test]
+    fn built_in_wave_schedule_is_valid() {
+        let spawner = MonsterSpawner::new();
+        assert_eq!(spawner.validate_monsters(&default_monsters()), Ok(()));
+    }
+
+    #[test]
+    fn waves_are_separated_by_gaps() {
+        let mut spawner =
+            MonsterSpawner::from_ron_str(include_str!("../../resources/waves/level_1.ron"))
+                .unwrap();
+        assert!(spawner.is_wave_spawned());
+        assert_eq!(spawner.wave, 0);
+
+        let mut wave_starts = Vec::new();
+        let mut wave_ends = Vec::new();
+        while !spawner.spawn_schedule.is_empty() {
+            let entry = spawner.spawn_schedule.remove(0);
+            if spawner.record_spawn(entry.time) {
+                wave_starts.push(entry.time);
+            }
+            if spawner.is_wave_spawned() {
+                wave_ends.push(entry.time);
             }
         }
+
+        assert_eq!(wave_starts, vec![0.0, 14.0]);
+        // After the last spawn of each wave.
+        assert_eq!(wave_ends, vec![8.8, 20.0]);
+        assert_eq!(spawner.wave, 2);
+    }
+
+    #[test]
+    fn spawns_far_apart_start_waves() {
+        let mut spawner = MonsterSpawner::from_ron_str(
+            "[
+                (monster: \"Chicken\", time: 0.0),
+                (monster: \"Chicken\", time: 3.0),
+                (monster: \"Chicken\", time: 7.0),
+                (monster: \"Chicken\", time: 7.5),
+                (monster: \"Chicken\", time: 11.0),
+            ]",
+        )
+        .unwrap();
+
+        let wave_starts: Vec<f32> = spawner
+            .spawn_schedule
+            .clone()
+            .into_iter()
+            .filter(|entry| spawner.record_spawn(entry.time))
+            .map(|entry| entry.time)
+            .collect();
+
+        assert_eq!(wave_starts, vec![0.0, 7.0, 11.0]);
+        assert_eq!(spawner.wave, 3);
+    }
+
+    fn chick(state: MonsterState) -> Box<GenericMonsterView> {
+        let mut monster = GenericMonster::new(MonsterDefinition::test_chick(), [0.0, 0.0], vec![]);
+        monster.state = state;
+        Box::new(GenericMonsterView {
+            monster,
+            animations: Animation {
+                current_sprite: 0,
+                next_sprite_interval: 0,
+                next_sprite_time: 0,
+                sprites: Vec::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn build_phase_waits_for_live_monsters() {
+        let basic = TowerRegistry::from_ron_str(include_str!("../../resources/towers/towers.ron"))
+            .unwrap()
+            .get("Basic")
+            .unwrap();
+        let mut board = Board::generate(0, 0);
+        let mut spawner = MonsterSpawner::from_ron_str("[]").unwrap();
+        spawner.record_spawn(0.0);
+        board.start_wave();
+        board.add_monster(chick(MonsterState::Walking));
+
+        // The wave spawned completely, but a monster of it is still alive.
+        spawner.update_phase(&mut board);
+        board.add_tower(Box::new(GenericTower::new(basic.clone(), [0.0, 3.0])));
+        let early = board.towers[0].get_id();
+        assert_eq!(board.sell_value(early), Some(7));
+
+        board.monster_views[0] = chick(MonsterState::Dead);
+        spawner.update_phase(&mut board);
+        board.add_tower(Box::new(GenericTower::new(basic, [0.0, 5.0])));
+        let late = board.towers[1].get_id();
+        assert_eq!(board.sell_value(late), Some(10));
+        assert_eq!(board.sell_value(early), Some(7));
+    }
+
+    #[test]
+    fn wave_schedule_unknown_monster() {
+        let spawner = MonsterSpawner::from_ron_str(
+            "[(monster: \"Chicken\", time: 0.0), (monster: \"Dragon\", time: 1.0)]",
+        )
+        .unwrap();
+        assert_eq!(
+            spawner.validate_monsters(&default_monsters()),
+            Err(LevelError::UnknownMonster {
+                index: 1,
+                monster: "Dragon".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn wave_schedule_not_chronological() {
+        let spawner = MonsterSpawner::from_ron_str(
+            "[(monster: \"Chicken\", time: 2.0), (monster: \"Chicken\", time: 1.0)]",
+        );
+        assert_eq!(
+            spawner.err(),
+            Some(LevelError::ScheduleNotChronological { index: 1 })
+        );
+    }
+
+    #[test]
+    fn wave_schedule_invalid_entrance() {
+        let spawner = MonsterSpawner::from_ron_str(
+            "[(monster: \"Chicken\", time: 0.0), (monster: \"Chicken\", time: 1.0, entrance: Some(2))]",
+        )
+        .unwrap();
+        assert_eq!(
+            spawner.validate_entrances(2),
+            Err(LevelError::InvalidEntrance {
+                index: 1,
+                entrance: 2
+            })
+        );
     }
 }
@@ -0,0 +1,18 @@
+use crate::level_system::LevelError;
+
+use ggez::{filesystem, Context};
+
+use std::io::Read;
+
+/// Reads a whole text file from the ggez resource directory, e.g.
+/// `/maps/level_1.ron`.
+pub fn read_resource(ctx: &mut Context, path: &str) -> Result<String, LevelError> {
+    let mut file =
+        filesystem::open(ctx, path).map_err(|e| LevelError::Io(format!("{}: {}", path, e)))?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| LevelError::Io(format!("{}: {}", path, e)))?;
+
+    Ok(contents)
+}
@@ -2,7 +2,7 @@ extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
-use crate::main_state::MainState;
+use crate::main_state::{MainState, DEFAULT_MAP};
 
 use ggez::{
     conf::{FullscreenType, WindowMode},
@@ -50,6 +50,11 @@ fn main() -> GameResult {
         .window_mode(mode);
     let (ctx, event_loop) = &mut cb.build()?;
 
-    let state = &mut MainState::new(ctx);
+    // Optionally play another map, e.g. `cargo run -- /maps/maze_1.ron`.
+    let map_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_MAP.to_string());
+
+    let state = &mut MainState::new(ctx, &map_path)?;
     event::run(ctx, event_loop, state)
 }
@@ -1,22 +1,34 @@
 use crate::asset_system::AssetManager;
 use crate::game_components::{
-    monsters::MonsterState,
-    towers::{BasicTower, NinjaTower, TowerType},
+    monsters::{MonsterEvent, MonsterRegistry, MonsterState},
+    towers::{GenericTower, TowerId, TowerRegistry},
     Board, Player, BLOCK_SIZE,
 };
-use crate::{level_system::monster_spawner::MonsterSpawner, ui_system::ui::*, utils::Scale};
+use crate::{
+    game_views::monsters::GenericMonsterView,
+    level_system::{monster_spawner::MonsterSpawner, LevelError, MapDefinition},
+    ui_system::ui::*,
+    utils::Scale,
+};
 
 use ggez::{
+    audio::SoundSource,
     event::{self, EventHandler, KeyCode, KeyMods},
-    graphics, Context, GameResult,
+    graphics, Context, GameError, GameResult,
 };
 
-use log::debug;
+use log::{debug, warn};
 
 use std::time;
 
+pub const DEFAULT_MAP: &str = "/maps/level_1.ron";
+pub const MONSTER_DEFINITIONS: &str = "/monsters/monsters.ron";
+pub const TOWER_DEFINITIONS: &str = "/towers/towers.ron";
+
 pub struct MainState {
     asset_manager: AssetManager,
+    monster_registry: MonsterRegistry,
+    tower_registry: TowerRegistry,
     player: Player,
     monster_spawner: MonsterSpawner,
     ui: UI,
@@ -25,17 +37,165 @@ pub struct MainState {
 }
 
 impl MainState {
-    pub fn new(ctx: &mut Context) -> MainState {
-        MainState {
-            asset_manager: AssetManager::new(ctx),
-            player: Player {
-                health: 100.0,
-                gold: 300,
-            },
-            monster_spawner: MonsterSpawner::new(),
-            ui: UI::new(),
-            board: Board::generate(1, 2),
+    /// Creates the game state playing the map at the given resource path,
+    /// e.g. `/maps/level_1.ron`. Fails if the monster or tower definitions,
+    /// or their assets, can't be loaded.
+    pub fn new(ctx: &mut Context, map_path: &str) -> GameResult<MainState> {
+        let monster_registry = MonsterRegistry::load(ctx, MONSTER_DEFINITIONS)
+            .map_err(|e| GameError::ResourceLoadError(format!("{}: {}", MONSTER_DEFINITIONS, e)))?;
+        let tower_registry = TowerRegistry::load(ctx, TOWER_DEFINITIONS)
+            .map_err(|e| GameError::ResourceLoadError(format!("{}: {}", TOWER_DEFINITIONS, e)))?;
+
+        let (board, monster_spawner) = match MainState::load_level(ctx, map_path, &monster_registry)
+        {
+            Ok(level) => level,
+            Err(e) => {
+                error!(
+                    "MainState: new: failed to load map {}, using generated board instead: {}",
+                    map_path, e
+                );
+                (Board::generate(1, 50), MonsterSpawner::new())
+            }
+        };
+
+        Ok(MainState {
+            asset_manager: AssetManager::new(ctx, &monster_registry, &tower_registry)?,
+            monster_registry,
+            ui: UI::new(&tower_registry),
+            tower_registry,
+            player: Player::new(100.0, 300),
+            monster_spawner,
+            board,
             time: time::Instant::now(),
+        })
+    }
+
+    /// Loads a map file and the wave schedule it references.
+    fn load_level(
+        ctx: &mut Context,
+        map_path: &str,
+        monster_registry: &MonsterRegistry,
+    ) -> Result<(Board, MonsterSpawner), LevelError> {
+        let map = MapDefinition::load(ctx, map_path)?;
+        let board = Board::from_map(&map)?;
+        let monster_spawner = MonsterSpawner::load(ctx, &map.wave_schedule)?;
+        monster_spawner.validate_entrances(board.spawn_points().len())?;
+        monster_spawner.validate_monsters(monster_registry)?;
+        Ok((board, monster_spawner))
+    }
+
+    /// Handles the events queued by monsters, e.g. bosses summoning minions.
+    fn handle_monster_events(&mut self) {
+        let events: Vec<MonsterEvent> = self
+            .board
+            .monster_views
+            .iter_mut()
+            .flat_map(|monster_view| monster_view.get_monster_mut().take_events())
+            .collect();
+
+        for event in events {
+            match event {
+                MonsterEvent::Summon {
+                    monster,
+                    count,
+                    position,
+                    route,
+                } => {
+                    let definition = match self.monster_registry.get(&monster) {
+                        Some(definition) => definition,
+                        None => {
+                            warn!("handle_monster_events: unknown monster {:?}.", monster);
+                            continue;
+                        }
+                    };
+                    for _ in 0..count {
+                        self.board.add_monster(Box::new(GenericMonsterView::new(
+                            definition.clone(),
+                            &self.asset_manager,
+                            position,
+                            route.clone(),
+                            0.0,
+                        )));
+                    }
+                }
+                MonsterEvent::AttackTower { tower, damage } => {
+                    self.board.damage_tower(tower, damage);
+                }
+                MonsterEvent::DisableTowers {
+                    position,
+                    range,
+                    duration,
+                } => self.board.disable_towers(position, range, duration),
+            }
+        }
+    }
+
+    /// Selects the tower with the given id, or clears the selection if there
+    /// is no such tower.
+    fn select_tower(&mut self, id: Option<TowerId>) {
+        self.ui.selected_tower = id.and_then(|id| {
+            Some(SelectedTower::new(
+                self.board.get_tower(id)?,
+                self.board.sell_value(id)?,
+            ))
+        });
+    }
+
+    /// Sells the selected tower, refunding (part of) the gold invested in it.
+    fn sell_selected_tower(&mut self) {
+        let id = match &self.ui.selected_tower {
+            Some(selected) => selected.id,
+            None => return,
+        };
+
+        if let Some(refund) = self.board.sell_tower(id) {
+            debug!(
+                "MainState: sell_selected_tower: sold tower {} for {} gold.",
+                id, refund
+            );
+            self.player.gold += refund;
+            self.asset_manager.item_assets.gold_sound.play().unwrap();
+        }
+        self.ui.selected_tower = None;
+    }
+
+    /// Buys the upgrade at index `choice` for the selected tower, if the
+    /// player can afford it.
+    fn upgrade_selected_tower(&mut self, choice: usize) {
+        let tower = match &self.ui.selected_tower {
+            Some(selected) => self.board.get_tower_mut(selected.id),
+            None => None,
+        };
+        let tower = match tower {
+            Some(tower) => tower,
+            None => return,
+        };
+
+        let cost = match tower.get_upgrades().get(choice) {
+            Some(upgrade) => upgrade.cost,
+            None => return,
+        };
+        if self.player.gold < cost || !tower.upgrade(choice) {
+            return;
+        }
+
+        self.player.gold -= cost;
+        debug!(
+            "MainState: upgrade_selected_tower: tower {} upgraded to level {} for {} gold.",
+            tower.get_id(),
+            tower.get_level(),
+            cost
+        );
+        let id = tower.get_id();
+        self.select_tower(Some(id));
+    }
+
+    fn scale(ctx: &Context) -> Scale {
+        let screen_rect = graphics::drawable_size(ctx);
+
+        Scale {
+            x: screen_rect.0 / 800.0, // 800.0 default width.
+            y: screen_rect.1 / 600.0, // 600.0 default height.
         }
     }
 }
@@ -45,16 +205,49 @@ impl EventHandler for MainState {
         let elapsed = self.time.elapsed().as_millis() as f32 / 1000.0;
         debug!("MainState: update: elapsed{}", elapsed);
 
-        self.monster_spawner.update(elapsed, &mut self.board, &self.asset_manager);
+        self.monster_spawner.update(
+            elapsed,
+            &mut self.board,
+            &self.asset_manager,
+            &self.monster_registry,
+        );
+
+        self.board.update_auras();
 
         for monster_view in self.board.monster_views.iter_mut() {
-            monster_view.get_monster_mut().update(
+            let monster = monster_view.get_monster_mut();
+            monster.update_status_effects(
                 elapsed,
-                &self.board.path_blocks,
-                &mut self.player,
+                &mut self.board.pickups,
+                &mut self.asset_manager,
             );
+            monster.update(elapsed, &mut self.player, &self.board.grid);
+
+            if monster.get_current_state() == MonsterState::Dead {
+                debug!(
+                    "MainState: update: monster died after walking {} pixels.",
+                    monster.distance_travelled()
+                );
+            }
         }
 
+        // Predict when the monster closest to the base will reach it.
+        self.ui.next_leak = self
+            .board
+            .monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .filter(|monster| monster.get_speed() > 0.0)
+            .map(|monster| monster.distance_remaining() / monster.get_speed())
+            .fold(None, |next: Option<f32>, time| {
+                Some(next.map_or(time, |next| next.min(time)))
+            });
+
+        // Before removing dead monsters, they may still have events queued
+        // such as spawning monsters on death. Monsters are never added to the
+        // board while the towers are iterating over them.
+        self.handle_monster_events();
+
         debug!(
             "MainState: update: monsters length before removing dead monsters: {}",
             self.board.monster_views.len()
@@ -67,14 +260,38 @@ impl EventHandler for MainState {
             self.board.monster_views.len()
         );
 
+        self.board.update_detection();
+        self.player.update(elapsed);
         for tower in self.board.towers.iter_mut() {
+            tower.set_damage_multiplier(self.player.damage_multiplier());
             tower.update(
                 elapsed,
-                &mut self.board.monster_views,
-                &mut self.board.gold_piles,
+                &self.board.monster_views,
+                &mut self.board.projectiles,
                 &mut self.asset_manager,
             );
         }
+        self.board
+            .update_projectiles(elapsed, &mut self.asset_manager);
+
+        // The selected tower may have been destroyed, and its sell value
+        // changes once a wave starts.
+        self.select_tower(self.ui.selected_tower.as_ref().map(|selected| selected.id));
+
+        // Show the health of the first boss on the board.
+        self.ui.boss_health = self
+            .board
+            .monster_views
+            .iter()
+            .map(|monster_view| monster_view.get_monster())
+            .find(|monster| monster.is_boss() && monster.get_current_state() != MonsterState::Dead)
+            .map(|boss| {
+                (
+                    boss.get_name().to_string(),
+                    boss.get_health() / boss.get_max_health(),
+                )
+            });
+
         self.time = time::Instant::now();
         Ok(())
     }
@@ -82,14 +299,26 @@ impl EventHandler for MainState {
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
         graphics::clear(ctx, [0.1, 0.2, 0.3, 1.0].into());
 
+        debug!("MainState: draw: drawing terrain.");
+        for block in self.board.terrain_blocks.iter_mut() {
+            block.draw(ctx)?;
+        }
+
         debug!("MainState: draw: drawing path blocks.");
         for block in self.board.path_blocks.iter_mut() {
             block.draw(ctx)?;
         }
 
-        debug!("MainState: draw: drawing monsters.");
+        debug!("MainState: draw: drawing obstacles.");
+        for obstacle in self.board.obstacles.iter_mut() {
+            obstacle.draw(ctx)?;
+        }
+
+        debug!("MainState: draw: drawing ground monsters.");
         for monster_view in self.board.monster_views.iter_mut() {
-            monster_view.draw(ctx, &self.asset_manager)?;
+            if !monster_view.get_monster().is_flying() {
+                monster_view.draw(ctx, &self.asset_manager)?;
+            }
         }
 
         debug!("MainState: draw: drawing towers.");
@@ -97,15 +326,22 @@ impl EventHandler for MainState {
             tower.draw(ctx, &self.asset_manager)?;
         }
 
-        debug!("MainState: draw: drawing tower attacks.");
-        // Draw tower attacks.
-        for tower in self.board.towers.iter_mut() {
-            tower.draw_abilities(ctx, &self.board.monster_views)?;
+        debug!("MainState: draw: drawing flying monsters.");
+        // Flying monsters are always drawn above towers.
+        for monster_view in self.board.monster_views.iter_mut() {
+            if monster_view.get_monster().is_flying() {
+                monster_view.draw(ctx, &self.asset_manager)?;
+            }
         }
 
-        debug!("MainState: draw: drawing gold piles.");
-        for gold_pile in self.board.gold_piles.iter_mut() {
-            gold_pile.draw(ctx, &self.asset_manager)?;
+        debug!("MainState: draw: drawing projectiles.");
+        for projectile in self.board.projectiles.iter() {
+            projectile.draw(ctx, &self.asset_manager)?;
+        }
+
+        debug!("MainState: draw: drawing pickups.");
+        for pickup in self.board.pickups.iter_mut() {
+            pickup.draw(ctx, &self.asset_manager)?;
         }
 
         debug!("MainState: draw: drawing base.");
@@ -123,10 +359,20 @@ impl EventHandler for MainState {
             ctx,
             x,
             y,
-            &mut self.board.gold_piles,
+            &mut self.board.pickups,
             &mut self.player,
             &mut self.asset_manager,
         );
+
+        // Show what it costs to clear the hovered obstacle, if any.
+        let scaled_position = MainState::scale(ctx).to_game_point(x, y);
+        self.ui.hovered_clear_cost = match self.ui.selected_tile_rect {
+            Some(_) => self
+                .board
+                .obstacle_at([scaled_position.x, scaled_position.y])
+                .map(|obstacle| obstacle.clear_cost),
+            None => None,
+        };
     }
 
     fn mouse_button_down_event(
@@ -141,44 +387,70 @@ impl EventHandler for MainState {
             _button, x, y
         );
 
-        if let Some(tower_type) = self.ui.hovering_on {
-            self.ui.selected_tile_type = tower_type;
+        if let Some(tower_type) = &self.ui.hovering_on {
+            self.ui.selected_tile_type = tower_type.clone();
         }
 
-        if let Some(_) = self.ui.selected_tile_rect {
-            let screen_rect = graphics::drawable_size(ctx);
+        let scaled_position = MainState::scale(ctx).to_game_point(x, y);
+        if let Some(button) = self.ui.button_at([scaled_position.x, scaled_position.y]) {
+            match button {
+                UIButton::Upgrade(choice) => self.upgrade_selected_tower(choice),
+                UIButton::Sell => self.sell_selected_tower(),
+            }
+            return;
+        }
+
+        if self.ui.selected_tile_rect.is_some() {
+            let click_position = [scaled_position.x, scaled_position.y];
+
+            // Clicking a placed tower selects it, clicking anywhere else
+            // clears the selection.
+            self.select_tower(self.board.tower_at(click_position));
+            if self.ui.selected_tower.is_some() {
+                return;
+            }
 
-            let scale = Scale {
-                x: screen_rect.0 / 800.0, // 800.0 default width.
-                y: screen_rect.1 / 600.0, // 600.0 default height.
+            // Clicking an obstacle pays to clear it, if the player can
+            // afford it.
+            if let Some(obstacle) = self.board.obstacle_at(click_position) {
+                let cost = obstacle.clear_cost;
+                if self.player.gold >= cost {
+                    self.player.gold -= cost;
+                    debug!(
+                        "MainState: mouse_button_down_event: clearing obstacle for {} gold.",
+                        cost
+                    );
+                    self.board.clear_obstacle(click_position);
+                    self.ui.hovered_clear_cost = None;
+                }
+                return;
+            }
+
+            let definition = match self.tower_registry.get(&self.ui.selected_tile_type) {
+                Some(definition) => definition,
+                None => return,
             };
-            let scaled_position = scale.to_game_point(x, y);
 
-            // Check that position is clear.
-            if !self
-                .board
-                .position_is_occupied([scaled_position.x, scaled_position.y])
-            {
+            // Check that position is clear, that the terrain allows the
+            // tower and, in maze mode, that the tower wouldn't block the
+            // monsters' way to the base.
+            if self.board.can_place_tower(click_position, &definition) {
                 let block_position = [
                     (scaled_position.x / BLOCK_SIZE).floor(),
                     (scaled_position.y / BLOCK_SIZE).floor(),
                 ];
 
-                if self.ui.selected_tile_type == TowerType::Basic {
-                    if self.player.gold >= 10 {
-                        self.player.gold -= 10;
-                        debug!("MainState: mouse_button_down_event: placing new BasicTower at x({}), y({}).", block_position[0], block_position[1]);
-                        self.board
-                            .add_tower(Box::new(BasicTower::new(block_position)));
-                    }
-                } else if self.ui.selected_tile_type == TowerType::Ninja {
-                    if self.player.gold >= 20 {
-                        self.player.gold -= 20;
-                        debug!("MainState: mouse_button_down_event: placing new NinjaTower at x({}), y({}).", block_position[0], block_position[1]);
-                        self.board
-                            .add_tower(Box::new(NinjaTower::new(block_position)));
-                    }
+                if self.player.gold < definition.cost {
+                    return;
                 }
+
+                self.player.gold -= definition.cost;
+                debug!(
+                    "MainState: mouse_button_down_event: placing new {} tower at x({}), y({}).",
+                    definition.name, block_position[0], block_position[1]
+                );
+                self.board
+                    .add_tower(Box::new(GenericTower::new(definition, block_position)));
             }
         }
     }
@@ -195,12 +467,35 @@ impl EventHandler for MainState {
             keycode, _keymods, _repeat
         );
 
-        if keycode == KeyCode::Key1 {
-            debug!("MainState: key_down_event: switching to TowerType::Basic.");
-            self.ui.selected_tile_type = TowerType::Basic;
-        } else if keycode == KeyCode::Key2 {
-            debug!("MainState: key_down_event: switching to TowerType::Ninja.");
-            self.ui.selected_tile_type = TowerType::Ninja;
+        if keycode == KeyCode::T {
+            let tower = match &mut self.ui.selected_tower {
+                Some(selected) => self.board.get_tower_mut(selected.id),
+                None => None,
+            };
+            if let Some(tower) = tower {
+                let targeting = tower.get_targeting().next();
+                debug!(
+                    "MainState: key_down_event: tower {} now targets {:?}.",
+                    tower.get_id(),
+                    targeting
+                );
+                tower.set_targeting(targeting);
+                let id = tower.get_id();
+                self.select_tower(Some(id));
+            }
+        } else if keycode == KeyCode::S {
+            self.sell_selected_tower();
+        }
+    }
+
+    /// Selects the tower with the typed hotkey for placement.
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) {
+        if let Some(definition) = self.tower_registry.by_hotkey(character) {
+            debug!(
+                "MainState: text_input_event: switching to {} tower.",
+                definition.name
+            );
+            self.ui.selected_tile_type = definition.name.clone();
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::{asset_system::AssetManager, game_components::towers::TowerType};
+use crate::{asset_system::AssetManager, game_components::towers::TowerDefinition};
 
 use ggez::{
     graphics::{self, DrawParam},
@@ -6,10 +6,12 @@ use ggez::{
     Context, GameResult,
 };
 
+use std::rc::Rc;
+
 pub const TOWER_ICON_SIZE: f32 = 50.0;
 
 pub struct TowerIcon {
-    pub tower_type: TowerType,
+    pub definition: Rc<TowerDefinition>,
 }
 
 impl TowerIcon {
@@ -20,26 +22,17 @@ impl TowerIcon {
         location: Point2<f32>,
         selected: bool,
     ) -> GameResult {
-        let asset = match self.tower_type {
-            TowerType::Basic => {
-                if selected {
-                    &asset_manager.builder_ui_assets.tower_selected_sprite
-                } else {
-                    &asset_manager.builder_ui_assets.tower_sprite
-                }
-            }
-            TowerType::Ninja => {
-                if selected {
-                    &asset_manager.builder_ui_assets.ninja_tower_selected_sprite
-                } else {
-                    &asset_manager.builder_ui_assets.ninja_tower_sprite
-                }
-            }
+        let path = if selected {
+            &self.definition.icon_selected
+        } else {
+            &self.definition.icon
         };
 
         // Destination isn't scaled by DrawParam.
         debug!("draw: location: {:?}", location);
-        graphics::draw(ctx, asset, DrawParam::default().dest(location))?;
+        if let Some(asset) = asset_manager.tower_assets.get_sprite(path) {
+            graphics::draw(ctx, asset, DrawParam::default().dest(location))?;
+        }
 
         Ok(())
     }
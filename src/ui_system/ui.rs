@@ -1,6 +1,9 @@
 use crate::{
     asset_system::AssetManager,
-    game_components::{towers::TowerType, GoldPile, Player, BLOCK_SIZE},
+    game_components::{
+        towers::{TargetingPolicy, Tower, TowerId, TowerRegistry, TowerType},
+        Pickup, Player, BLOCK_SIZE,
+    },
     ui_system::tower_icon::{TowerIcon, TOWER_ICON_SIZE},
     utils::Scale,
 };
@@ -22,6 +25,69 @@ const GOLD_Y: f32 = 30.0;
 const HP_X: f32 = 30.0;
 const HP_Y: f32 = 50.0;
 
+const NEXT_LEAK_X: f32 = 30.0;
+const NEXT_LEAK_Y: f32 = 70.0;
+
+const DAMAGE_BUFF_X: f32 = 30.0;
+const DAMAGE_BUFF_Y: f32 = 90.0;
+
+const TARGETING_X: f32 = 30.0;
+const TARGETING_Y: f32 = 110.0;
+
+/// Space between the build bar and the upgrade/sell buttons.
+const UPGRADES_MARGIN: f32 = 40.0;
+const UPGRADES_Y: f32 = 10.0;
+const BUTTON_WIDTH: f32 = 300.0;
+const BUTTON_HEIGHT: f32 = 25.0;
+const BUTTON_SPACING: f32 = 30.0;
+
+const TOOLTIP_WIDTH: f32 = 420.0;
+const TOOLTIP_HEIGHT: f32 = 65.0;
+
+const BOSS_BAR_WIDTH: f32 = 400.0;
+const BOSS_BAR_HEIGHT: f32 = 16.0;
+const BOSS_BAR_Y: f32 = 10.0;
+
+/// A placed tower selected by clicking on it.
+pub struct SelectedTower {
+    pub id: TowerId,
+    pub center: [f32; 2],
+    pub range: f32,
+    pub targeting: TargetingPolicy,
+    pub level: u32,
+    /// Name and cost of each upgrade available for the tower.
+    pub upgrades: Vec<(String, u32)>,
+    /// Gold refunded when selling the tower.
+    pub sell_value: u32,
+}
+
+impl SelectedTower {
+    pub fn new(tower: &dyn Tower, sell_value: u32) -> SelectedTower {
+        SelectedTower {
+            id: tower.get_id(),
+            center: tower.get_center_pos_abs(),
+            range: tower.attack_range(),
+            targeting: tower.get_targeting(),
+            level: tower.get_level(),
+            upgrades: tower
+                .get_upgrades()
+                .iter()
+                .map(|upgrade| (upgrade.name.clone(), upgrade.cost))
+                .collect(),
+            sell_value,
+        }
+    }
+}
+
+/// Buttons of the UI bar.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum UIButton {
+    /// Buys the upgrade with the given index for the selected tower.
+    Upgrade(usize),
+    /// Sells the selected tower.
+    Sell,
+}
+
 pub struct UI {
     position: Point2<f32>,
     rect: Rect,
@@ -29,6 +95,13 @@ pub struct UI {
     pub hovering_on: Option<TowerType>,
     pub selected_tile_rect: Option<[f32; 2]>,
     pub selected_tile_type: TowerType,
+    /// Cost of clearing the obstacle under the mouse, if any.
+    pub hovered_clear_cost: Option<u32>,
+    /// Seconds until the next monster reaches the base, if any are walking.
+    pub next_leak: Option<f32>,
+    /// Name and remaining health (fraction) of the boss on the board, if any.
+    pub boss_health: Option<(String, f32)>,
+    pub selected_tower: Option<SelectedTower>,
 }
 
 /// UI responsible for drawing the status bar and build bar. All
@@ -36,7 +109,9 @@ pub struct UI {
 /// (0, screen_height-UI_HEIGHT)
 /// and scaled.
 impl UI {
-    pub fn new() -> UI {
+    /// Creates the UI with a build bar icon for every tower in the registry,
+    /// the first one selected for placement.
+    pub fn new(tower_registry: &TowerRegistry) -> UI {
         UI {
             position: Point2 { x: 0.0, y: 0.0 },
             rect: Rect {
@@ -45,17 +120,24 @@ impl UI {
                 w: 0.0,
                 h: 0.0,
             },
-            build_bar: vec![
-                TowerIcon {
-                    tower_type: TowerType::Basic,
-                },
-                TowerIcon {
-                    tower_type: TowerType::Ninja,
-                },
-            ],
+            build_bar: tower_registry
+                .definitions()
+                .iter()
+                .map(|definition| TowerIcon {
+                    definition: definition.clone(),
+                })
+                .collect(),
             hovering_on: None,
             selected_tile_rect: None,
-            selected_tile_type: TowerType::Basic,
+            selected_tile_type: tower_registry
+                .definitions()
+                .first()
+                .map(|definition| definition.name.clone())
+                .unwrap_or_default(),
+            hovered_clear_cost: None,
+            next_leak: None,
+            boss_health: None,
+            selected_tower: None,
         }
     }
 
@@ -84,8 +166,13 @@ impl UI {
         self.draw_background(ctx)?;
         self.draw_gold(ctx, player)?;
         self.draw_hp(ctx, player)?;
+        self.draw_next_leak(ctx)?;
+        self.draw_damage_buff(ctx, player)?;
         self.draw_build_bar(ctx, asset_manager)?;
+        self.draw_tooltip(ctx)?;
         self.draw_selected_tile(ctx)?;
+        self.draw_selected_tower(ctx, player)?;
+        self.draw_boss_health(ctx)?;
         Ok(())
     }
 
@@ -127,6 +214,171 @@ impl UI {
         Ok(())
     }
 
+    fn draw_next_leak(&mut self, ctx: &mut Context) -> GameResult {
+        let next_leak = match self.next_leak {
+            Some(next_leak) => next_leak,
+            None => return Ok(()),
+        };
+
+        let text = graphics::Text::new(format!("NEXT LEAK: {:.1}s", next_leak));
+        let location = (Point2 {
+            x: NEXT_LEAK_X,
+            y: self.position.y + NEXT_LEAK_Y,
+        },);
+        graphics::draw(ctx, &text, location)?;
+        Ok(())
+    }
+
+    fn draw_damage_buff(&mut self, ctx: &mut Context, player: &Player) -> GameResult {
+        if player.damage_buff_time <= 0.0 {
+            return Ok(());
+        }
+
+        let text = graphics::Text::new(format!(
+            "DAMAGE x{:.1}: {:.1}s",
+            player.damage_buff, player.damage_buff_time
+        ));
+        let location = (Point2 {
+            x: DAMAGE_BUFF_X,
+            y: self.position.y + DAMAGE_BUFF_Y,
+        },);
+        graphics::draw(ctx, &text, location)?;
+        Ok(())
+    }
+
+    /// Outlines the range of the selected tower and shows its targeting
+    /// policy.
+    fn draw_selected_tower(&mut self, ctx: &mut Context, player: &Player) -> GameResult {
+        let selected = match &self.selected_tower {
+            Some(selected) => selected,
+            None => return Ok(()),
+        };
+
+        let range = graphics::Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::stroke(2.0),
+            selected.center,
+            selected.range,
+            0.5,
+            graphics::Color::new(1.0, 1.0, 0.4, 0.6),
+        )?;
+        graphics::draw(ctx, &range, DrawParam::default())?;
+
+        let text = graphics::Text::new(format!("TARGET: {} (T)", selected.targeting.name()));
+        let location = (Point2 {
+            x: TARGETING_X,
+            y: self.position.y + TARGETING_Y,
+        },);
+        graphics::draw(ctx, &text, location)?;
+
+        let text = graphics::Text::new(format!("LEVEL {}", selected.level));
+        let location = (Point2 {
+            x: self.upgrades_x(),
+            y: self.position.y + UPGRADES_Y,
+        },);
+        graphics::draw(ctx, &text, location)?;
+
+        for (index, (name, cost)) in selected.upgrades.iter().enumerate() {
+            let rect = self.button_rect(index);
+            let color = if player.gold >= *cost {
+                graphics::Color::new(0.2, 0.5, 0.2, 1.0)
+            } else {
+                graphics::Color::new(0.3, 0.3, 0.3, 1.0)
+            };
+            let button =
+                graphics::Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), rect, color)?;
+            graphics::draw(ctx, &button, DrawParam::default())?;
+
+            let text = graphics::Text::new(format!("UPGRADE: {} ({} gold)", name, cost));
+            let location = (Point2 {
+                x: rect.x + 5.0,
+                y: rect.y + 5.0,
+            },);
+            graphics::draw(ctx, &text, location)?;
+        }
+
+        let rect = self.button_rect(selected.upgrades.len());
+        let button = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            rect,
+            graphics::Color::new(0.5, 0.2, 0.2, 1.0),
+        )?;
+        graphics::draw(ctx, &button, DrawParam::default())?;
+
+        let text = graphics::Text::new(format!("SELL: {} gold (S)", selected.sell_value));
+        let location = (Point2 {
+            x: rect.x + 5.0,
+            y: rect.y + 5.0,
+        },);
+        graphics::draw(ctx, &text, location)?;
+        Ok(())
+    }
+
+    /// X position of the upgrade/sell button column, right of the last
+    /// build bar icon.
+    fn upgrades_x(&self) -> f32 {
+        BUILD_BAR_POSITION.x + self.build_bar.len() as f32 * TOWER_ICON_SIZE + UPGRADES_MARGIN
+    }
+
+    /// Rect of the button with the given index in the button column next to
+    /// the build bar.
+    fn button_rect(&self, index: usize) -> Rect {
+        Rect::new(
+            self.upgrades_x(),
+            self.position.y + UPGRADES_Y + 20.0 + index as f32 * BUTTON_SPACING,
+            BUTTON_WIDTH,
+            BUTTON_HEIGHT,
+        )
+    }
+
+    /// The button at the given (scaled) position, if any.
+    pub fn button_at(&self, position: [f32; 2]) -> Option<UIButton> {
+        let selected = self.selected_tower.as_ref()?;
+        let upgrades = selected.upgrades.len();
+        match (0..=upgrades).find(|&index| self.button_rect(index).contains(position))? {
+            index if index == upgrades => Some(UIButton::Sell),
+            index => Some(UIButton::Upgrade(index)),
+        }
+    }
+
+    /// Draws a large health bar of the current boss at the top of the game
+    /// area.
+    fn draw_boss_health(&mut self, ctx: &mut Context) -> GameResult {
+        let (name, health) = match &self.boss_health {
+            Some(boss_health) => boss_health,
+            None => return Ok(()),
+        };
+
+        let x = (self.rect.w - BOSS_BAR_WIDTH) / 2.0;
+        let background = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            [0.0, 0.0, BOSS_BAR_WIDTH, BOSS_BAR_HEIGHT].into(),
+            graphics::Color::new(0.2, 0.0, 0.0, 0.8),
+        )?;
+        graphics::draw(ctx, &background, (Point2 { x, y: BOSS_BAR_Y },))?;
+
+        let width = BOSS_BAR_WIDTH * health.clamp(0.0, 1.0);
+        if width > 0.0 {
+            let bar = graphics::Mesh::new_rectangle(
+                ctx,
+                graphics::DrawMode::fill(),
+                [0.0, 0.0, width, BOSS_BAR_HEIGHT].into(),
+                graphics::Color::new(0.8, 0.1, 0.1, 1.0),
+            )?;
+            graphics::draw(ctx, &bar, (Point2 { x, y: BOSS_BAR_Y },))?;
+        }
+
+        let text = graphics::Text::new(name.as_str());
+        let location = (Point2 {
+            x: x + 5.0,
+            y: BOSS_BAR_Y,
+        },);
+        graphics::draw(ctx, &text, location)?;
+        Ok(())
+    }
+
     /// Draws all tower icons inside the build_bar which can fit inside the UI.
     /// Overflowing tower icons are not drawn.
     fn draw_build_bar(&mut self, ctx: &mut Context, asset_manager: &AssetManager) -> GameResult {
@@ -142,7 +394,7 @@ impl UI {
                 ctx,
                 asset_manager,
                 offset,
-                self.hovering_on == Some(tower.tower_type),
+                self.hovering_on.as_ref() == Some(&tower.definition.name),
             )?;
             // Only tiles in x direction for now.
             offset.x += TOWER_ICON_SIZE;
@@ -151,6 +403,62 @@ impl UI {
         Ok(())
     }
 
+    /// Describes the tower hovered over in the build bar, just above the UI.
+    fn draw_tooltip(&mut self, ctx: &mut Context) -> GameResult {
+        let hovering_on = match &self.hovering_on {
+            Some(hovering_on) => hovering_on,
+            None => return Ok(()),
+        };
+        let definition = match self
+            .build_bar
+            .iter()
+            .find(|tower| tower.definition.name == *hovering_on)
+        {
+            Some(tower) => &tower.definition,
+            None => return Ok(()),
+        };
+
+        let rect = Rect::new(
+            BUILD_BAR_POSITION.x,
+            self.position.y - TOOLTIP_HEIGHT,
+            TOOLTIP_WIDTH,
+            TOOLTIP_HEIGHT,
+        );
+        let background = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            rect,
+            graphics::Color::new(0.1, 0.15, 0.2, 0.9),
+        )?;
+        graphics::draw(ctx, &background, DrawParam::default())?;
+
+        let stats = &definition.stats;
+        let mut text = graphics::Text::new(format!(
+            "{} ({}): {} gold\n",
+            definition.name.to_uppercase(),
+            definition.hotkey.to_ascii_uppercase(),
+            definition.cost
+        ));
+        text.add(format!(
+            "RANGE {} DAMAGE {} ({:?}) EVERY {:.1}s\n",
+            stats.attack_range, stats.damage.amount, stats.damage.damage_type, stats.attack_timer
+        ));
+        text.add(definition.description.as_str());
+        text.set_bounds(
+            Point2 {
+                x: TOOLTIP_WIDTH - 10.0,
+                y: TOOLTIP_HEIGHT,
+            },
+            graphics::Align::Left,
+        );
+        let location = (Point2 {
+            x: rect.x + 5.0,
+            y: rect.y + 5.0,
+        },);
+        graphics::draw(ctx, &text, location)?;
+        Ok(())
+    }
+
     fn draw_selected_tile(&mut self, ctx: &mut Context) -> GameResult {
         if let Some(tile) = self.selected_tile_rect {
             let rectangle = graphics::Mesh::new_rectangle(
@@ -165,6 +473,15 @@ impl UI {
                 y: tile[1],
             },);
             graphics::draw(ctx, &rectangle, location)?;
+
+            if let Some(cost) = self.hovered_clear_cost {
+                let text = graphics::Text::new(format!("Clear: {} gold", cost));
+                let location = (Point2 {
+                    x: tile[0],
+                    y: tile[1] - 15.0,
+                },);
+                graphics::draw(ctx, &text, location)?;
+            }
         }
         Ok(())
     }
@@ -174,7 +491,7 @@ impl UI {
         ctx: &Context,
         x: f32,
         y: f32,
-        gold_piles: &mut Vec<GoldPile>,
+        pickups: &mut Vec<Pickup>,
         player: &mut Player,
         asset_manager: &mut AssetManager,
     ) {
@@ -189,12 +506,12 @@ impl UI {
             scale,
             x,
             y,
-            gold_piles.len()
+            pickups.len()
         );
 
         // Check inside game window.
         if x > 0.0 && x < screen_rect.0 && y > 0.0 && y < screen_rect.1 - UI_HEIGHT {
-            self.handle_in_game_hover(scale, x, y, gold_piles, player, asset_manager);
+            self.handle_in_game_hover(scale, x, y, pickups, player, asset_manager);
         } else {
             self.handle_ui_bar_hover(scale, x, y);
         }
@@ -205,7 +522,7 @@ impl UI {
         scale: Scale,
         x: f32,
         y: f32,
-        gold_piles: &mut Vec<GoldPile>,
+        pickups: &mut Vec<Pickup>,
         player: &mut Player,
         asset_manager: &mut AssetManager,
     ) {
@@ -217,16 +534,16 @@ impl UI {
             (y / (BLOCK_SIZE * scale.y)).floor() * BLOCK_SIZE,
         ]);
 
-        // Check for any gold to pick up.
-        gold_piles.retain(|gold_pile| {
+        // Check for any loot to pick up.
+        pickups.retain(|pickup| {
             let scaled_xy = scale.to_game_point(x, y);
 
-            let xd = scaled_xy.x - (gold_pile.position[0] + 35.0 / 2.0);
-            let yd = scaled_xy.y - (gold_pile.position[1] + 35.0 / 2.0);
+            let xd = scaled_xy.x - (pickup.position[0] + 35.0 / 2.0);
+            let yd = scaled_xy.y - (pickup.position[1] + 35.0 / 2.0);
 
             // Within 20px radius.
             if xd * xd + yd * yd < 20.0 * 20.0 {
-                player.gold += gold_pile.value;
+                player.pick_up(pickup.kind);
                 asset_manager.item_assets.gold_sound.play().unwrap();
                 false
             } else {
@@ -257,7 +574,7 @@ impl UI {
                     && y > vp_build_bar_position.y * scale.y
                     && y < vp_build_bar_position.y + TOWER_ICON_SIZE * scale.y
                 {
-                    self.hovering_on = Some(tower.tower_type);
+                    self.hovering_on = Some(tower.definition.name.clone());
                     return;
                 }
                 cumulative_width += TOWER_ICON_SIZE * scale.x;